@@ -0,0 +1,245 @@
+use std::fmt::Write;
+use std::io::IsTerminal;
+
+use owo_colors::{OwoColorize, Style};
+
+use crate::parser::{ParseError, ParseErrorKind};
+use crate::sema::{SemaError, SemaErrorKind};
+use crate::span::{SourceMap, Span};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+}
+
+impl Severity {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+        }
+    }
+
+    fn style(self) -> Style {
+        match self {
+            Self::Error => Style::new().red().bold(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub span: Span,
+    pub message: Option<String>,
+}
+
+impl Label {
+    pub fn new(span: Span) -> Self {
+        Self { span, message: None }
+    }
+
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+}
+
+/// A renderable error pointing into a source file.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub primary: Label,
+    pub secondary: Vec<Label>,
+    pub help: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn error(message: impl Into<String>, primary: Label) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: message.into(),
+            primary,
+            secondary: vec![],
+            help: vec![],
+        }
+    }
+
+    pub fn with_secondary(mut self, label: Label) -> Self {
+        self.secondary.push(label);
+        self
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help.push(help.into());
+        self
+    }
+
+    /// Renders the diagnostic, coloring it only if stdout is a terminal.
+    pub fn render_auto(&self, map: &SourceMap) -> String {
+        self.render(map, std::io::stdout().is_terminal())
+    }
+
+    /// Renders the diagnostic Rust-style: a header, the location and the offending source lines
+    /// with the labels underlined below them.
+    pub fn render(&self, map: &SourceMap, color: bool) -> String {
+        let paint = |text: &str, style: Style| {
+            if color {
+                text.style(style).to_string()
+            } else {
+                text.to_string()
+            }
+        };
+        let accent = Style::new().blue().bold();
+        let severity_style = self.severity.style();
+
+        let mut labels: Vec<(&Label, bool)> = vec![(&self.primary, true)];
+        labels.extend(self.secondary.iter().map(|label| (label, false)));
+
+        let line_of = |label: &Label| map.location(label.span.start).line;
+        let mut lines: Vec<usize> = labels.iter().map(|(label, _)| line_of(label)).collect();
+        lines.sort_unstable();
+        lines.dedup();
+
+        let gutter_width = lines.last().copied().unwrap_or(1).to_string().len();
+        let gutter = " ".repeat(gutter_width);
+        let bar = paint("|", accent);
+
+        let mut out = String::new();
+        let header = format!("{}:", self.severity.name());
+        let _ = writeln!(out, "{} {}", paint(&header, severity_style), paint(&self.message, Style::new().bold()));
+
+        let location = map.location(self.primary.span.start);
+        let _ = writeln!(
+            out,
+            "{gutter}{} {}:{}:{}",
+            paint("-->", accent),
+            map.name(),
+            location.line,
+            location.col
+        );
+        let _ = writeln!(out, "{gutter} {bar}");
+
+        for (idx, &line) in lines.iter().enumerate() {
+            if idx > 0 && lines[idx - 1] + 1 != line {
+                let _ = writeln!(out, "{}", paint("...", accent));
+            }
+
+            let text = map.line(line).unwrap_or("");
+            let line_no = format!("{line:>gutter_width$}");
+            let _ = writeln!(out, "{} {bar} {text}", paint(&line_no, accent));
+
+            // Labels are drawn left to right, so the rows read in source order
+            let mut line_labels: Vec<&(&Label, bool)> =
+                labels.iter().filter(|(label, _)| line_of(label) == line).collect();
+            line_labels.sort_by_key(|(label, _)| label.span.start);
+            for (label, is_primary) in line_labels {
+                let (marker, style) = if *is_primary {
+                    ('^', severity_style)
+                } else {
+                    ('-', accent)
+                };
+
+                let line_start = map.line_start(line).unwrap_or(0);
+                let line_end = line_start + text.len();
+                let start = label.span.start.clamp(line_start, line_end);
+                let end = label.span.end.clamp(start, line_end);
+
+                let padding = " ".repeat(map.source()[line_start..start].chars().count());
+                let width = map.source()[start..end].chars().count().max(1);
+                let mut underline = marker.to_string().repeat(width);
+                if let Some(message) = &label.message {
+                    underline.push(' ');
+                    underline.push_str(message);
+                }
+
+                let _ = writeln!(out, "{gutter} {bar} {padding}{}", paint(&underline, style));
+            }
+        }
+
+        if !self.help.is_empty() {
+            let _ = writeln!(out, "{gutter} {bar}");
+        }
+        for help in &self.help {
+            let _ = writeln!(out, "{gutter} {} {} {help}", paint("=", accent), paint("help:", Style::new().bold()));
+        }
+
+        out
+    }
+}
+
+impl From<&ParseError> for Diagnostic {
+    fn from(err: &ParseError) -> Self {
+        let primary = Label::new(err.span);
+        let message = err.kind.to_string();
+
+        match &err.kind {
+            ParseErrorKind::ExpectedDifferentToken { expected, .. } => {
+                Self::error(message, primary.with_message(format!("expected {expected}")))
+            }
+            ParseErrorKind::ExpectedDifferentTokens { .. } | ParseErrorKind::UnexpectedToken(_) => {
+                Self::error(message, primary.with_message("unexpected token"))
+            }
+            ParseErrorKind::ErrorToken => Self::error(message, primary.with_message("not a valid token")),
+            ParseErrorKind::ExpectedToken => Self::error(message, primary.with_message("file ends here")),
+            ParseErrorKind::MissingTerminatingToken { expected, opened } => {
+                Self::error(message, primary.with_message(format!("expected {expected}")))
+                    .with_secondary(Label::new(*opened).with_message("opened here"))
+            }
+            ParseErrorKind::MissingTypeAnnot => Self::error(message, primary)
+                .with_help("add a type annotation, e.g. `name: Type`"),
+            ParseErrorKind::MissingCodeBlock => {
+                Self::error(message, primary).with_help("add a body: `{ ... }` or `=> <expr>`")
+            }
+            ParseErrorKind::MissingAssignment => Self::error(message, primary)
+                .with_help("`var` declarations need an initial value, e.g. `var name: Type = value`"),
+            ParseErrorKind::NotAssignable => Self::error(message, primary.with_message("can't be assigned to")),
+            ParseErrorKind::NonAssociativeOperator(_) => Self::error(message, primary)
+                .with_help("add parentheses or combine the comparisons with `&&`"),
+            ParseErrorKind::IfExprWithoutElse => Self::error(message, primary.with_message("has no else branch"))
+                .with_help("add a value for when the condition is false: `else <value>`"),
+            ParseErrorKind::InvalidLoopVariable => Self::error(message, primary.with_message("not an identifier"))
+                .with_help("bind each element to a name, e.g. `for item in items`"),
+            ParseErrorKind::DuplicateGroupDecl { first } => {
+                Self::error(message, primary.with_message("second group declaration"))
+                    .with_secondary(Label::new(*first).with_message("first declared here"))
+            }
+            ParseErrorKind::LiteralOutOfRange(ty) => {
+                Self::error(message, primary.with_message(format!("doesn't fit into `{ty}`")))
+            }
+            ParseErrorKind::InvalidEscape(_) => Self::error(message, primary.with_message("invalid escape"))
+                .with_help(r#"valid escapes are \n \t \r \0 \\ \" \' \{ \}, \xNN and \u{NNNN}"#),
+            ParseErrorKind::EmptyInterpolation => Self::error(message, primary)
+                .with_help(r#"write `\{` for a literal brace"#),
+            ParseErrorKind::EmptyCharLiteral | ParseErrorKind::MultipleCharsInCharLiteral => {
+                Self::error(message, primary).with_help("use a string literal `\"...\"` for text")
+            }
+            ParseErrorKind::Unknown | ParseErrorKind::ParseInt(_) | ParseErrorKind::ParseFloat(_) => {
+                Self::error(message, primary)
+            }
+        }
+    }
+}
+
+impl From<&SemaError> for Diagnostic {
+    fn from(err: &SemaError) -> Self {
+        let primary = Label::new(err.span);
+        let message = err.kind.to_string();
+
+        match &err.kind {
+            SemaErrorKind::DuplicateFieldInit { first, .. } => Self::error(message, primary)
+                .with_secondary(Label::new(*first).with_message("first initialised here")),
+            SemaErrorKind::UnknownField { .. } => Self::error(message, primary.with_message("unknown field")),
+            SemaErrorKind::MissingFieldInit { .. } => Self::error(message, primary),
+            SemaErrorKind::PrivateFieldInit { declared, .. } => Self::error(message, primary)
+                .with_secondary(Label::new(*declared).with_message("declared here"))
+                .with_help("mark the field `pub` or construct the object from within its group"),
+            SemaErrorKind::ExpectedBool { .. } => Self::error(message, primary.with_message("not a Bool")),
+            SemaErrorKind::DuplicateTypeParam { first, .. } => Self::error(message, primary)
+                .with_secondary(Label::new(*first).with_message("first declared here")),
+            SemaErrorKind::GenericArgCount { expected, declared, .. } => {
+                Self::error(message, primary.with_message(format!("expected {expected}")))
+                    .with_secondary(Label::new(*declared).with_message("declared here"))
+            }
+        }
+    }
+}
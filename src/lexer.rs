@@ -1,16 +1,123 @@
-use logos::Logos;
+use std::fmt;
+use std::ops::Range;
 
-#[derive(Logos, Debug, Clone, Copy, PartialEq, strum_macros::Display)]
+use logos::{Lexer, Logos};
+
+/// A piece of a string literal's body, as a byte range relative to the body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StrPart {
+    /// Literal text, still containing its escape sequences.
+    Text(Range<usize>),
+    /// The source of an interpolated expression, without its braces.
+    Interpolation(Range<usize>),
+}
+
+/// Splits a lexed string literal, quotes included, into its text and interpolated parts.
+pub fn str_parts(literal: &str) -> Vec<StrPart> {
+    scan_str(&literal[1..]).map(|(_, parts)| parts).unwrap_or_default()
+}
+
+/// Scans the rest of a string literal after its opening quote, returning its length up to and
+/// including the closing quote along with the parts of its body. Interpolations may contain
+/// string and char literals of their own, so quotes and braces are matched instead of being
+/// lexed by a regex.
+fn scan_str(rest: &str) -> Option<(usize, Vec<StrPart>)> {
+    let bytes = rest.as_bytes();
+    let mut parts = vec![];
+    let mut depth = 0usize;
+    let mut part_start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => {
+                i += 1;
+                // `\u{...}` is an escape, not an interpolation
+                if rest[i..].starts_with("u{") {
+                    i += rest[i..].find('}').unwrap_or(0);
+                }
+            }
+            b'"' if depth == 0 => {
+                if part_start < i {
+                    parts.push(StrPart::Text(part_start..i));
+                }
+                return Some((i + 1, parts));
+            }
+            b'"' => i += scan_str(&rest[i + 1..])?.0,
+            b'\'' if depth > 0 => {
+                i += 1;
+                while *bytes.get(i)? != b'\'' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+            }
+            // An empty `{}` is literal text, as in `"{}".format(x)`
+            b'{' if depth == 0 && bytes.get(i + 1) == Some(&b'}') => i += 1,
+            b'{' => {
+                if depth == 0 {
+                    if part_start < i {
+                        parts.push(StrPart::Text(part_start..i));
+                    }
+                    part_start = i + 1;
+                }
+                depth += 1;
+            }
+            b'}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    parts.push(StrPart::Interpolation(part_start..i));
+                    part_start = i + 1;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    None
+}
+
+fn lex_str(lex: &mut Lexer<Token>) -> bool {
+    match scan_str(lex.remainder()) {
+        Some((len, _)) => {
+            lex.bump(len);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Lexes the rest of a raw string after its opening `r#*"`, which is terminated by a quote
+/// followed by as many hashes as it was opened with.
+fn lex_raw_str(lex: &mut Lexer<Token>) -> bool {
+    let hashes = lex.slice().len() - 2;
+    let terminator = format!("\"{}", "#".repeat(hashes));
+
+    match lex.remainder().find(&terminator) {
+        Some(idx) => {
+            lex.bump(idx + terminator.len());
+            true
+        }
+        None => false,
+    }
+}
+
+#[derive(Logos, Debug, Clone, Copy, PartialEq)]
 pub enum Token {
     // Keywords
     #[token("fun")]
     Fun,
     #[token("class")]
     Class,
+    #[token("interface")]
+    Interface,
+    #[token("trait")]
+    Trait,
     #[token("val")]
     Val,
     #[token("var")]
     Var,
+    #[token("let")]
+    Let,
     #[token("pub")]
     Pub,
     #[token("priv")]
@@ -19,6 +126,8 @@ pub enum Token {
     Import,
     #[token("group")]
     Group,
+    #[token("as")]
+    As,
     #[token("ret")]
     Ret,
     #[token("new")]
@@ -35,6 +144,10 @@ pub enum Token {
     For,
     #[token("in")]
     In,
+    #[token("break")]
+    Break,
+    #[token("continue")]
+    Continue,
     #[token("true")]
     True,
     #[token("false")]
@@ -45,20 +158,33 @@ pub enum Token {
     Ident,
 
     // Literals
-    #[regex(r"[0-9]([0-9_]*[0-9])?u")]
+    #[regex(r"[0-9]([0-9_]*[0-9])?u(8|16|32|64)?")]
+    #[regex(r"0x_*[0-9a-fA-F][0-9a-fA-F_]*u(8|16|32|64)?")]
+    #[regex(r"0b_*[01][01_]*u(8|16|32|64)?")]
+    #[regex(r"0o_*[0-7][0-7_]*u(8|16|32|64)?")]
     UIntLiteral,
-    #[regex(r"[0-9]([0-9_]*[0-9])?")]
+    #[regex(r"[0-9]([0-9_]*[0-9])?(i8|i16|i32|i64)?")]
+    #[regex(r"0x_*[0-9a-fA-F][0-9a-fA-F_]*(i8|i16|i32|i64)?")]
+    #[regex(r"0b_*[01][01_]*(i8|i16|i32|i64)?")]
+    #[regex(r"0o_*[0-7][0-7_]*(i8|i16|i32|i64)?")]
     IntLiteral,
-    #[regex(r"[0-9][_0-9]*\.[0-9][_0-9]*([eE][+-]?[0-9][_0-9]*)?", priority = 2)]
+    #[regex(r"[0-9][_0-9]*\.[0-9][_0-9]*([eE][+-]?[0-9][_0-9]*)?(f32|f64)?", priority = 2)]
+    #[regex(r"[0-9]([0-9_]*[0-9])?(f32|f64)")]
     FloatLiteral,
-    #[regex(r#""([^"\\]|\\.)*""#)] // simple double quoted strings with escapes
+    #[token("\"", lex_str)] // double quoted strings with escapes and `{}` interpolations
     StrLiteral,
+    #[regex(r#"r#*""#, lex_raw_str)]
+    RawStrLiteral,
+    #[regex(r"'([^'\\]|\\.)*'")]
+    CharLiteral,
 
     // Operators
     #[token("+")]
     Plus,
     #[token("-")]
     Minus,
+    #[token("->")]
+    Arrow,
     #[token("*")]
     Star,
     #[token("/")]
@@ -66,8 +192,31 @@ pub enum Token {
     #[token("%")]
     Percent,
 
+    #[token("+=")]
+    PlusEquals,
+    #[token("-=")]
+    MinusEquals,
+    #[token("*=")]
+    StarEquals,
+    #[token("/=")]
+    SlashEquals,
+    #[token("%=")]
+    PercentEquals,
+    #[token("&=")]
+    AmpEquals,
+    #[token("|=")]
+    PipeEquals,
+    #[token("^=")]
+    CaretEquals,
+    #[token("<<=")]
+    ShiftLeftEquals,
+    #[token(">>=")]
+    ShiftRightEquals,
+
     #[token("=")]
     Equals,
+    #[token("=>")]
+    FatArrow,
     #[token("==")]
     EqualsEquals,
     #[token("!=")]
@@ -88,6 +237,19 @@ pub enum Token {
     #[token("!")]
     Not,
 
+    #[token("&")]
+    Amp,
+    #[token("|")]
+    Pipe,
+    #[token("^")]
+    Caret,
+    #[token("~")]
+    Tilde,
+    #[token("<<")]
+    ShiftLeft,
+    #[token(">>")]
+    ShiftRight,
+
     // Punctuation
     #[token(";")]
     Semicolon,
@@ -97,6 +259,8 @@ pub enum Token {
     Comma,
     #[token(".")]
     Dot,
+    #[token("?")]
+    Question,
 
     #[token("(")]
     LeftParen,
@@ -121,3 +285,89 @@ pub enum Token {
     #[regex(r"[ \t\n\f]+", logos::skip)]
     Whitespace,
 }
+
+/// Shows a token as it's written in source, e.g. `` `}` ``, or the kind of token it is.
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Self::Fun => "`fun`",
+            Self::Class => "`class`",
+            Self::Interface => "`interface`",
+            Self::Trait => "`trait`",
+            Self::Val => "`val`",
+            Self::Var => "`var`",
+            Self::Let => "`let`",
+            Self::Pub => "`pub`",
+            Self::Priv => "`priv`",
+            Self::Import => "`import`",
+            Self::Group => "`group`",
+            Self::As => "`as`",
+            Self::Ret => "`ret`",
+            Self::New => "`new`",
+            Self::At => "`@`",
+            Self::If => "`if`",
+            Self::Else => "`else`",
+            Self::While => "`while`",
+            Self::For => "`for`",
+            Self::In => "`in`",
+            Self::Break => "`break`",
+            Self::Continue => "`continue`",
+            Self::True => "`true`",
+            Self::False => "`false`",
+            Self::Ident => "identifier",
+            Self::UIntLiteral => "unsigned int literal",
+            Self::IntLiteral => "int literal",
+            Self::FloatLiteral => "float literal",
+            Self::StrLiteral => "string literal",
+            Self::RawStrLiteral => "raw string literal",
+            Self::CharLiteral => "char literal",
+            Self::Plus => "`+`",
+            Self::Minus => "`-`",
+            Self::Arrow => "`->`",
+            Self::Star => "`*`",
+            Self::Slash => "`/`",
+            Self::Percent => "`%`",
+            Self::PlusEquals => "`+=`",
+            Self::MinusEquals => "`-=`",
+            Self::StarEquals => "`*=`",
+            Self::SlashEquals => "`/=`",
+            Self::PercentEquals => "`%=`",
+            Self::AmpEquals => "`&=`",
+            Self::PipeEquals => "`|=`",
+            Self::CaretEquals => "`^=`",
+            Self::ShiftLeftEquals => "`<<=`",
+            Self::ShiftRightEquals => "`>>=`",
+            Self::Equals => "`=`",
+            Self::FatArrow => "`=>`",
+            Self::EqualsEquals => "`==`",
+            Self::NotEquals => "`!=`",
+            Self::LessThan => "`<`",
+            Self::LessThanEquals => "`<=`",
+            Self::GreaterThan => "`>`",
+            Self::GreaterThanEquals => "`>=`",
+            Self::And => "`&&`",
+            Self::Or => "`||`",
+            Self::Not => "`!`",
+            Self::Amp => "`&`",
+            Self::Pipe => "`|`",
+            Self::Caret => "`^`",
+            Self::Tilde => "`~`",
+            Self::ShiftLeft => "`<<`",
+            Self::ShiftRight => "`>>`",
+            Self::Semicolon => "`;`",
+            Self::Colon => "`:`",
+            Self::Comma => "`,`",
+            Self::Dot => "`.`",
+            Self::Question => "`?`",
+            Self::LeftParen => "`(`",
+            Self::RightParen => "`)`",
+            Self::LeftBrace => "`{`",
+            Self::RightBrace => "`}`",
+            Self::LeftBracket => "`[`",
+            Self::RightBracket => "`]`",
+            Self::CommentLine | Self::CommentBlock => "comment",
+            Self::Whitespace => "whitespace",
+        };
+        f.write_str(text)
+    }
+}
@@ -0,0 +1,8 @@
+pub mod diagnostics;
+pub mod lexer;
+pub mod parser;
+pub mod sema;
+pub mod span;
+
+#[cfg(test)]
+mod tests;
@@ -1,14 +1,39 @@
-mod parser;
-mod lexer;
-mod tests;
-
-use owo_colors::OwoColorize;
+use duklang::diagnostics::Diagnostic;
+use duklang::parser::Parser;
+use duklang::sema;
+use duklang::span::SourceMap;
 use rustyline::{DefaultEditor, Result};
-use parser::Parser;
+
+fn parse_and_report(map: &SourceMap) -> bool {
+    let (ast, errors) = Parser::new(map.source()).parse_module();
+    if !errors.is_empty() {
+        for err in &errors {
+            print!("{}", Diagnostic::from(err).render_auto(map));
+        }
+        return false;
+    }
+
+    let modules = [ast];
+    let errors = sema::check(&modules);
+    for err in &errors {
+        print!("{}", Diagnostic::from(err).render_auto(map));
+    }
+
+    println!("Parsed AST: {:#?}", modules[0]);
+    errors.is_empty()
+}
 
 fn main() -> Result<()> {
     env_logger::init();
 
+    if let Some(path) = std::env::args().nth(1) {
+        let source = std::fs::read_to_string(&path)?;
+        if !parse_and_report(&SourceMap::new(path, source)) {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     let mut rl = DefaultEditor::new()?;
 
     loop {
@@ -17,11 +42,7 @@ fn main() -> Result<()> {
         match line {
             Ok(source) => {
                 rl.add_history_entry(source.as_str())?;
-                let ast_res = Parser::new(&source).parse_let_decl();
-                match ast_res {
-                    Ok(ast) => println!("Parsed AST: {:#?}", ast),
-                    Err(err) => println!("{}{}", "Syntax error: ".red(), err),
-                }
+                parse_and_report(&SourceMap::new("<repl>", source));
             }
             Err(rustyline::error::ReadlineError::Interrupted) |
             Err(rustyline::error::ReadlineError::Eof) => break, // Ctrl+C or Ctrl+D
@@ -1,11 +1,20 @@
-use std::num::{ParseFloatError, ParseIntError};
+use std::fmt;
+use std::num::{IntErrorKind, ParseFloatError, ParseIntError};
 
 use logos::{Lexer, Logos};
 
-use crate::lexer::Token;
+use crate::lexer::{StrPart, Token, str_parts};
+use crate::span::Span;
 
 #[derive(thiserror::Error, Debug)]
-pub enum ParseError {
+#[error("{kind}")]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub span: Span,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ParseErrorKind {
     #[error("Unknown")]
     Unknown,
 
@@ -30,8 +39,8 @@ pub enum ParseError {
     #[error("Parse float error: {0}")]
     ParseFloat(#[from] ParseFloatError),
 
-    #[error("Missing terminating token: {0}")]
-    MissingTerminatingToken(Token),
+    #[error("Missing terminating token: {expected}")]
+    MissingTerminatingToken { expected: Token, opened: Span },
 
     #[error("A type annotation is missing")]
     MissingTypeAnnot,
@@ -41,15 +50,45 @@ pub enum ParseError {
 
     #[error("A assignment is missing")]
     MissingAssignment,
+
+    #[error("Literal out of range for `{0}`")]
+    LiteralOutOfRange(NumSuffix),
+
+    #[error("Invalid escape sequence: {0}")]
+    InvalidEscape(String),
+
+    #[error("An interpolation must contain an expression")]
+    EmptyInterpolation,
+
+    #[error("A char literal must not be empty")]
+    EmptyCharLiteral,
+
+    #[error("A char literal must contain exactly one character")]
+    MultipleCharsInCharLiteral,
+
+    #[error("The left side of an assignment must be a variable, a field or an index")]
+    NotAssignable,
+
+    #[error("Operator {0} can't be chained with operators of the same precedence")]
+    NonAssociativeOperator(Token),
+
+    #[error("An if expression needs an else branch")]
+    IfExprWithoutElse,
+
+    #[error("The loop variable must be an identifier")]
+    InvalidLoopVariable,
+
+    #[error("A module can only belong to one group")]
+    DuplicateGroupDecl { first: Span },
 }
 
-impl From<()> for ParseError {
-    fn from(_: ()) -> Self {
-        Self::ErrorToken
+impl ParseErrorKind {
+    pub fn at(self, span: Span) -> ParseError {
+        ParseError { kind: self, span }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum VisibilityAnnot {
     Default, // Defaults to Private
     Private,
@@ -58,83 +97,251 @@ pub enum VisibilityAnnot {
 
 type CodeBlock = Vec<RuntimeStatement>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Module {
+    /// The group the module's declarations belong to, if it declares one.
+    pub group: Option<GroupDecl>,
+    pub imports: Vec<ImportDecl>,
     pub decls: Vec<GroupMemberStatement>,
+    pub span: Span,
 }
 
-#[derive(Debug)]
+// group <path>;
+#[derive(Debug, Clone)]
+pub struct GroupDecl {
+    pub path: Vec<String>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub enum ImportKind {
+    /// `import A.B;` or `import A.B as C;`. The path names either a single item or a whole
+    /// group, which can't be told apart before the imported group is resolved.
+    Named { alias: Option<String> },
+    /// `import A.*;` imports every public member of the group `A`.
+    Glob,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportDecl {
+    pub path: Vec<String>,
+    pub kind: ImportKind,
+    pub span: Span,
+}
+
+impl ImportDecl {
+    /// The name the import is visible as in the importing module, if it isn't a glob.
+    pub fn binding(&self) -> Option<&str> {
+        match &self.kind {
+            ImportKind::Named { alias: Some(alias) } => Some(alias),
+            ImportKind::Named { alias: None } => self.path.last().map(String::as_str),
+            ImportKind::Glob => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum GroupMemberStatement {
     Class(ClassDeclStatement),
+    Interface(InterfaceDeclStatement),
     Fun(FunDeclStatement),
     Let(ValDeclStatement),
 }
 
-#[derive(Debug)]
+impl GroupMemberStatement {
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Class(decl) => decl.span,
+            Self::Interface(decl) => decl.span,
+            Self::Fun(decl) => decl.span,
+            Self::Let(decl) => decl.span,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum ClassMemberStatement {
     Fun(FunDeclStatement),
     Let(ValDeclStatement),
+    Var(VarDeclStatement),
 }
 
-#[derive(Debug)]
+impl ClassMemberStatement {
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Fun(decl) => decl.span,
+            Self::Let(decl) => decl.span,
+            Self::Var(decl) => decl.span,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum RuntimeStatement {
     Let(ValDeclStatement),
+    Var(VarDeclStatement),
     Discard(Expr),
-    Return(Option<Expr>),
+    Return { value: Option<Expr>, span: Span },
+    If(IfStatement),
+    While(WhileStatement),
+    For(ForStatement),
+    Break(Span),
+    Continue(Span),
 }
 
-#[derive(Debug)]
+impl RuntimeStatement {
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Let(decl) => decl.span,
+            Self::Var(decl) => decl.span,
+            Self::Discard(expr) => expr.span,
+            Self::Return { span, .. } => *span,
+            Self::If(statement) => statement.span,
+            Self::While(statement) => statement.span,
+            Self::For(statement) => statement.span,
+            Self::Break(span) | Self::Continue(span) => *span,
+        }
+    }
+
+    /// Statements ending with a code block aren't terminated by a `;`.
+    pub fn needs_semicolon(&self) -> bool {
+        !matches!(self, Self::If(_) | Self::While(_) | Self::For(_))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IfStatement {
+    pub arms: Vec<IfArm>,
+    pub else_code: Option<CodeBlock>,
+    pub span: Span,
+}
+
+/// The `if <cond> { ... }` and each `else if <cond> { ... }` of an if statement.
+#[derive(Debug, Clone)]
+pub struct IfArm {
+    pub cond: Expr,
+    pub code: CodeBlock,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct WhileStatement {
+    pub cond: Expr,
+    pub code: CodeBlock,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct ForStatement {
+    pub iterator: String,
+    pub iterable: Expr,
+    pub code: CodeBlock,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
 pub struct ClassDeclStatement {
-    pub attributes: Vec<String>,
+    pub attributes: Vec<AttributeAnnot>,
     pub visibility: VisibilityAnnot,
 
     pub name: Option<String>,
-    pub decls: Vec<GroupMemberStatement>,
+    pub type_params: Vec<TypeParam>,
+    pub parents: Vec<TypeExpr>,
+    pub decls: Vec<ClassMemberStatement>,
+    pub span: Span,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterfaceKind {
+    Interface,
+    Trait,
+}
+
+#[derive(Debug, Clone)]
+pub struct InterfaceDeclStatement {
+    pub attributes: Vec<AttributeAnnot>,
+    pub visibility: VisibilityAnnot,
+
+    pub kind: InterfaceKind,
+    pub name: String,
+    pub type_params: Vec<TypeParam>,
+    pub parents: Vec<TypeExpr>,
+    pub methods: Vec<FunSignature>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
 pub struct ValDeclStatement {
-    pub attributes: Vec<String>,
+    pub attributes: Vec<AttributeAnnot>,
     pub visibility: VisibilityAnnot,
 
     pub name: String,
-    pub type_annot: Option<String>,
+    pub type_annot: Option<TypeExpr>,
     pub initial_assignment: Option<Expr>,
+    pub span: Span,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct VarDeclStatement {
     pub attributes: Vec<AttributeAnnot>,
     pub visibility: VisibilityAnnot,
 
     pub name: String,
-    pub type_annot: String,
+    pub type_annot: Option<TypeExpr>,
     pub initial_assignment: Expr,
+    pub span: Span,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FunDeclStatement {
     pub attributes: Vec<AttributeAnnot>,
     pub visibility: VisibilityAnnot,
 
     pub name: Option<String>,
-    pub ret_type: Option<String>,
+    pub type_params: Vec<TypeParam>,
+    pub ret_type: Option<TypeExpr>,
     pub args: Vec<ArgDecl>,
+    /// The function body. An arrow body `=> <v>` is stored as a single `ret <v>` statement.
     pub code: CodeBlock,
+    pub span: Span,
 }
 
-#[derive(Debug)]
+/// A body-less function declaration, as required by interfaces.
+#[derive(Debug, Clone)]
+pub struct FunSignature {
+    pub attributes: Vec<AttributeAnnot>,
+    pub visibility: VisibilityAnnot,
+
+    pub name: Option<String>,
+    pub type_params: Vec<TypeParam>,
+    pub ret_type: Option<TypeExpr>,
+    pub args: Vec<ArgDecl>,
+    pub span: Span,
+}
+
+/// A generic type parameter, e.g. `T: Animal + Named`.
+#[derive(Debug, Clone)]
+pub struct TypeParam {
+    pub name: String,
+    /// The interfaces and traits the type has to implement.
+    pub bounds: Vec<TypeExpr>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
 pub struct AttributeAnnot {
     pub name: String,
     pub args: Vec<Expr>,
+    pub span: Span,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ArgDecl {
     pub attributes: Vec<String>,
 
     pub name: String,
-    pub type_name: String,
+    pub type_annot: TypeExpr,
+    pub span: Span,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -145,6 +352,12 @@ pub enum BinOp {
     Div, // x / y
     Mod, // x % y
 
+    BitAnd,     // x & y
+    BitOr,      // x | y
+    BitXor,     // x ^ y
+    ShiftLeft,  // x << y
+    ShiftRight, // x >> y
+
     Equals,       // x == y
     NotEquals,    // x != y
     Greater,      // x > y
@@ -152,10 +365,130 @@ pub enum BinOp {
     GreaterEqual, // x >= y
     LowerEqual,   // x <= y
 
-    Assign, // x = y
+    And, // x && y, short-circuiting
+    Or,  // x || y, short-circuiting
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Assoc {
+    Left,
+    /// Chaining operators of the same precedence, e.g. `a < b < c`, is an error.
+    None,
+}
+
+/// Binary operator precedence, from the loosest to the tightest binding:
+///
+/// | Precedence | Operators                    | Associativity |
+/// |------------|------------------------------|---------------|
+/// | 1          | `=` and compound assignments | right         |
+/// | 2          | `\|\|`                       | left          |
+/// | 3          | `&&`                         | left          |
+/// | 4          | `==` `!=` `<` `>` `<=` `>=`  | none          |
+/// | 5          | `\|`                         | left          |
+/// | 6          | `^`                          | left          |
+/// | 7          | `&`                          | left          |
+/// | 8          | `<<` `>>`                    | left          |
+/// | 9          | `+` `-`                      | left          |
+/// | 10         | `*` `/` `%`                  | left          |
+///
+/// Assignments aren't binary operators but `ExprKind::Assign` expressions, parsed by
+/// `Parser::parse_expr` before the binary operators. Unary operators bind tighter than any
+/// binary operator, postfix calls, member accesses and indexing tighter still.
+impl BinOp {
+    pub fn from_token(tok: Token) -> Option<Self> {
+        let op = match tok {
+            Token::Or => Self::Or,
+            Token::And => Self::And,
+            Token::EqualsEquals => Self::Equals,
+            Token::NotEquals => Self::NotEquals,
+            Token::LessThan => Self::Lower,
+            Token::GreaterThan => Self::Greater,
+            Token::LessThanEquals => Self::LowerEqual,
+            Token::GreaterThanEquals => Self::GreaterEqual,
+            Token::Pipe => Self::BitOr,
+            Token::Caret => Self::BitXor,
+            Token::Amp => Self::BitAnd,
+            Token::ShiftLeft => Self::ShiftLeft,
+            Token::ShiftRight => Self::ShiftRight,
+            Token::Plus => Self::Add,
+            Token::Minus => Self::Sub,
+            Token::Star => Self::Mul,
+            Token::Slash => Self::Div,
+            Token::Percent => Self::Mod,
+            _ => return None,
+        };
+
+        Some(op)
+    }
+
+    /// The operator applied by a compound assignment token, e.g. `Add` for `+=`.
+    pub fn from_compound_assign_token(tok: Token) -> Option<Self> {
+        let op = match tok {
+            Token::PlusEquals => Self::Add,
+            Token::MinusEquals => Self::Sub,
+            Token::StarEquals => Self::Mul,
+            Token::SlashEquals => Self::Div,
+            Token::PercentEquals => Self::Mod,
+            Token::AmpEquals => Self::BitAnd,
+            Token::PipeEquals => Self::BitOr,
+            Token::CaretEquals => Self::BitXor,
+            Token::ShiftLeftEquals => Self::ShiftLeft,
+            Token::ShiftRightEquals => Self::ShiftRight,
+            _ => return None,
+        };
+
+        Some(op)
+    }
+
+    pub fn precedence(&self) -> u8 {
+        match self {
+            Self::Or => 2,
+            Self::And => 3,
+            Self::Equals
+            | Self::NotEquals
+            | Self::Lower
+            | Self::Greater
+            | Self::LowerEqual
+            | Self::GreaterEqual => 4,
+            Self::BitOr => 5,
+            Self::BitXor => 6,
+            Self::BitAnd => 7,
+            Self::ShiftLeft | Self::ShiftRight => 8,
+            Self::Add | Self::Sub => 9,
+            Self::Mul | Self::Div | Self::Mod => 10,
+        }
+    }
+
+    pub fn associativity(&self) -> Assoc {
+        match self.precedence() {
+            4 => Assoc::None,
+            _ => Assoc::Left,
+        }
+    }
+
+    /// The attribute marking a method as the overload of this operator, if it can be
+    /// overloaded directly.
+    pub fn overload_attribute(&self) -> Option<&'static str> {
+        match self {
+            Self::Add => Some("add"),
+            Self::Sub => Some("sub"),
+            Self::Mul => Some("mul"),
+            Self::Div => Some("div"),
+            Self::Mod => Some("mod"),
+            Self::BitAnd => Some("bitAnd"),
+            Self::BitOr => Some("bitOr"),
+            Self::BitXor => Some("bitXor"),
+            Self::ShiftLeft => Some("shl"),
+            Self::ShiftRight => Some("shr"),
+            Self::Equals => Some("eq"),
+            Self::Greater => Some("greater"),
+            Self::Lower => Some("lower"),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum UnaryOp {
     Not,      // !x
     Positive, // +x
@@ -163,21 +496,108 @@ pub enum UnaryOp {
     BitNot,   // ~x
 }
 
+impl UnaryOp {
+    /// The attribute marking a method as the overload of this operator, if it can be
+    /// overloaded directly.
+    pub fn overload_attribute(&self) -> Option<&'static str> {
+        match self {
+            Self::Not => None,
+            Self::Positive => Some("pos"),
+            Self::Negative => Some("neg"),
+            Self::BitNot => Some("bitNot"),
+        }
+    }
+}
+
+/// The type given to a numeric literal by its suffix, like `u8` in `255u8`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum NumSuffix {
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    F32,
+    F64,
+}
+
+impl NumSuffix {
+    /// Returns `None` for an empty suffix and for the plain `u` of an unsized unsigned literal.
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        Some(match suffix {
+            "i8" => Self::I8,
+            "i16" => Self::I16,
+            "i32" => Self::I32,
+            "i64" => Self::I64,
+            "u8" => Self::U8,
+            "u16" => Self::U16,
+            "u32" => Self::U32,
+            "u64" => Self::U64,
+            "f32" => Self::F32,
+            "f64" => Self::F64,
+            _ => return None,
+        })
+    }
+
+    pub fn bits(self) -> u32 {
+        match self {
+            Self::I8 | Self::U8 => 8,
+            Self::I16 | Self::U16 => 16,
+            Self::I32 | Self::U32 | Self::F32 => 32,
+            Self::I64 | Self::U64 | Self::F64 => 64,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum LiteralExpr {
-    Int(i64),
-    UInt(u64),
-    Float(f64),
+    Int(i64, Option<NumSuffix>),
+    UInt(u64, Option<NumSuffix>),
+    Float(f64, Option<NumSuffix>),
     Str(String),
+    Char(char),
+    Bool(bool),
+}
+
+#[derive(Debug, Clone)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+}
+
+impl Expr {
+    pub fn new(kind: ExprKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+
+    /// Returns `true` if the expression can be assigned to: a variable, a field or an index.
+    pub fn is_assignable(&self) -> bool {
+        matches!(
+            self.kind,
+            ExprKind::Read(_) | ExprKind::Member { .. } | ExprKind::Index { .. }
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
-pub enum Expr {
+pub enum ExprKind {
     Read(String),
     Call {
-        callee: String,
+        callee: Box<Expr>,
         args: Vec<Expr>,
     },
+    Member {
+        target: Box<Expr>,
+        name: String,
+    },
+    Index {
+        target: Box<Expr>,
+        index: Box<Expr>,
+    },
     Unary {
         val: Box<Expr>,
         op: UnaryOp,
@@ -187,21 +607,327 @@ pub enum Expr {
         right: Box<Expr>,
         op: BinOp,
     },
+    /// `target = value`, or a compound assignment like `target += value` if `op` is set.
+    Assign {
+        target: Box<Expr>,
+        op: Option<BinOp>,
+        value: Box<Expr>,
+    },
     Literal(LiteralExpr),
+    /// A string literal with `{expr}` interpolations, e.g. `"Hi {name}!"`.
+    Interpolated(Vec<InterpolatedPart>),
+    Array(Vec<Expr>),
+    /// `[key: value, ...]`, in source order.
+    Dict(Vec<(Expr, Expr)>),
+    If(IfExpr),
+    Block(BlockExpr),
+    /// An anonymous function, e.g. `fun(x: Int) => x * x`. It's never named.
+    Lambda(Box<FunDeclStatement>),
+    New {
+        type_expr: TypeExpr,
+        fields: Vec<FieldInit>,
+    },
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+pub enum InterpolatedPart {
+    Str(String),
+    Expr(Expr),
+}
+
+/// A `<n>: <v>` field initialiser of a `new` expression.
+#[derive(Debug, Clone)]
+pub struct FieldInit {
+    pub name: String,
+    pub value: Expr,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub struct TypeExpr {
+    pub kind: TypeExprKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub enum TypeExprKind {
+    /// A possibly qualified type name with generic arguments, e.g. `Http.Response` or `Result<Int>`.
+    Path {
+        segments: Vec<String>,
+        generics: Vec<TypeExpr>,
+    },
+    /// `(A, B) -> R`
+    Function {
+        args: Vec<TypeExpr>,
+        ret: Box<TypeExpr>,
+    },
+    /// `[T]`
+    Array(Box<TypeExpr>),
+    /// `[K: V]`
+    Dict {
+        key: Box<TypeExpr>,
+        value: Box<TypeExpr>,
+    },
+    /// `T?`
+    Optional(Box<TypeExpr>),
+}
+
+/// Writes `items` separated by commas.
+fn write_list<T: fmt::Display>(f: &mut fmt::Formatter<'_>, items: &[T]) -> fmt::Result {
+    for (idx, item) in items.iter().enumerate() {
+        if idx > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{item}")?;
+    }
+    Ok(())
+}
+
+/// Formats the type the way it's written in source, e.g. `[Str: (Int) -> Bool]`.
+impl fmt::Display for TypeExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            TypeExprKind::Path { segments, generics } => {
+                write!(f, "{}", segments.join("."))?;
+                if !generics.is_empty() {
+                    write!(f, "<")?;
+                    write_list(f, generics)?;
+                    write!(f, ">")?;
+                }
+                Ok(())
+            }
+            TypeExprKind::Function { args, ret } => {
+                write!(f, "(")?;
+                write_list(f, args)?;
+                write!(f, ") -> {ret}")
+            }
+            TypeExprKind::Array(element) => write!(f, "[{element}]"),
+            TypeExprKind::Dict { key, value } => write!(f, "[{key}: {value}]"),
+            TypeExprKind::Optional(inner) if matches!(inner.kind, TypeExprKind::Function { .. }) => {
+                write!(f, "({inner})?")
+            }
+            TypeExprKind::Optional(inner) => write!(f, "{inner}?"),
+        }
+    }
+}
+
+/// A code block used as an expression, valued by its trailing expression without a `;`.
+#[derive(Debug, Clone)]
+pub struct BlockExpr {
+    pub code: CodeBlock,
+    pub value: Option<Box<Expr>>,
+}
+
+#[derive(Debug, Clone)]
 pub struct IfExpr {
     pub cond: Box<Expr>,
     pub arm_then: Box<Expr>,
     pub arm_else: Box<Expr>,
+    pub span: Span,
+}
+
+/// Decodes the escape sequences in the body of a string or char literal starting at the byte
+/// offset `offset`, erroring on invalid escapes.
+fn unescape(body: &str, offset: usize) -> Result<String, ParseError> {
+    let mut value = String::with_capacity(body.len());
+    let mut chars = body.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+
+        let mut end = start + 1;
+        let mut take = |chars: &mut std::iter::Peekable<std::str::CharIndices>| {
+            let (idx, c) = chars.next()?;
+            end = idx + c.len_utf8();
+            Some(c)
+        };
+
+        let decoded = match take(&mut chars) {
+            Some('n') => Some('\n'),
+            Some('t') => Some('\t'),
+            Some('r') => Some('\r'),
+            Some('0') => Some('\0'),
+            Some('\\') => Some('\\'),
+            Some('"') => Some('"'),
+            Some('\'') => Some('\''),
+            Some('{') => Some('{'),
+            Some('}') => Some('}'),
+            Some('x') => {
+                let digits: String = (0..2).filter_map(|_| take(&mut chars)).collect();
+                u8::from_str_radix(&digits, 16)
+                    .ok()
+                    .filter(|byte| digits.len() == 2 && byte.is_ascii())
+                    .map(char::from)
+            }
+            Some('u') => {
+                let mut digits = String::new();
+                let mut closed = false;
+                if take(&mut chars) == Some('{') {
+                    while let Some(c) = take(&mut chars) {
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+                        digits.push(c);
+                    }
+                }
+                u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .filter(|_| closed && (1..=6).contains(&digits.len()))
+                    .and_then(char::from_u32)
+            }
+            _ => None,
+        };
+
+        match decoded {
+            Some(c) => value.push(c),
+            None => {
+                let span = Span::new(offset + start, offset + end);
+                return Err(ParseErrorKind::InvalidEscape(body[start..end].to_string()).at(span));
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+/// A `{ ... }` block whose last statement is kept aside until it's known whether the value of
+/// the block is used.
+struct PendingBlock {
+    code: CodeBlock,
+    tail: Option<Tail>,
+    span: Span,
+}
+
+/// The statement right before the closing `}` of a block, if it may be the block's value.
+enum Tail {
+    /// An expression without `;`.
+    Expr(Expr),
+    /// An if statement, which is the value if it has an `else`.
+    If(IfChain),
+}
+
+impl PendingBlock {
+    /// Turns the block into a block expression for a position where its value is used.
+    fn into_block_expr(self) -> Result<Expr, ParseError> {
+        let mut code = self.code;
+        let value = match self.tail {
+            None => None,
+            Some(Tail::Expr(expr)) => Some(expr),
+            Some(Tail::If(chain)) if chain.else_arm.is_some() => Some(chain.into_expr()?),
+            Some(Tail::If(chain)) => {
+                code.push(RuntimeStatement::If(chain.into_statement()?));
+                None
+            }
+        };
+
+        let block = BlockExpr {
+            code,
+            value: value.map(Box::new),
+        };
+        Ok(Expr::new(ExprKind::Block(block), self.span))
+    }
+
+    /// Turns the block into the code of a statement, which has no value, so a trailing
+    /// expression without `;` is discarded.
+    fn into_code(self) -> Result<CodeBlock, ParseError> {
+        let mut code = self.code;
+        match self.tail {
+            None => {}
+            Some(Tail::Expr(expr)) => code.push(RuntimeStatement::Discard(expr)),
+            Some(Tail::If(chain)) => code.push(RuntimeStatement::If(chain.into_statement()?)),
+        }
+        Ok(code)
+    }
+}
+
+/// An `if` at the start of a statement, which is an if statement unless it turns out to be
+/// the value of its block.
+struct IfChain {
+    /// The condition, the block and the start of each `if` and `else if`.
+    arms: Vec<(Expr, PendingBlock, usize)>,
+    else_arm: Option<ElseArm>,
+    span: Span,
+}
+
+enum ElseArm {
+    Block(Box<PendingBlock>),
+    /// A brace-less `if <cond> <v> else <v>`, which can only be a value, along with where the
+    /// `{` of an if statement was expected.
+    Braceless(Expr, Span),
+}
+
+impl IfChain {
+    fn into_expr(self) -> Result<Expr, ParseError> {
+        let mut value = match self.else_arm {
+            Some(ElseArm::Block(block)) => block.into_block_expr()?,
+            Some(ElseArm::Braceless(expr, _)) => expr,
+            None => {
+                let keyword = Span::new(self.span.start, self.span.start + "if".len());
+                return Err(ParseErrorKind::IfExprWithoutElse.at(keyword));
+            }
+        };
+
+        for (cond, block, arm_start) in self.arms.into_iter().rev() {
+            let span = Span::new(arm_start, value.span.end);
+            let if_expr = IfExpr {
+                cond: Box::new(cond),
+                arm_then: Box::new(block.into_block_expr()?),
+                arm_else: Box::new(value),
+                span,
+            };
+            value = Expr::new(ExprKind::If(if_expr), span);
+        }
+        Ok(value)
+    }
+
+    fn into_statement(self) -> Result<IfStatement, ParseError> {
+        let else_code = match self.else_arm {
+            None => None,
+            Some(ElseArm::Block(block)) => Some(block.into_code()?),
+            Some(ElseArm::Braceless(_, expected)) => return Err(ParseErrorKind::MissingCodeBlock.at(expected)),
+        };
+
+        let arms = self
+            .arms
+            .into_iter()
+            .map(|(cond, block, arm_start)| {
+                Ok(IfArm {
+                    span: Span::new(arm_start, block.span.end),
+                    cond,
+                    code: block.into_code()?,
+                })
+            })
+            .collect::<Result<_, ParseError>>()?;
+
+        Ok(IfStatement {
+            arms,
+            else_code,
+            span: self.span,
+        })
+    }
+}
+
+/// The attributes and visibility preceding a declaration.
+struct DeclHeader {
+    attributes: Vec<AttributeAnnot>,
+    visibility: VisibilityAnnot,
+    start: usize,
 }
 
+type PeekedToken<'source> = (Result<Token, ()>, Span, &'source str);
+
 #[derive(Debug)]
 pub struct Parser<'source> {
     lexer: Lexer<'source, Token>,
-    peeked: Option<Result<Token, ()>>,
-    current_token: usize,
+    peeked: Option<PeekedToken<'source>>,
+    prev_span: Span,
+    prev_slice: &'source str,
+    consumed: usize,
+    errors: Vec<ParseError>,
 }
 
 impl<'source> Parser<'source> {
@@ -209,17 +935,27 @@ impl<'source> Parser<'source> {
         Self {
             lexer: Token::lexer(source),
             peeked: None,
-            current_token: 0,
+            prev_span: Span::point(0),
+            prev_slice: "",
+            consumed: 0,
+            errors: Vec::new(),
         }
     }
 
+    fn lex(&mut self) -> Option<PeekedToken<'source>> {
+        let tok = self.lexer.next()?;
+        Some((tok, self.lexer.span().into(), self.lexer.slice()))
+    }
+
     fn next(&mut self) -> Option<Result<Token, ()>> {
-        if let Some(tok) = self.peeked.take() {
-            Some(tok)
-        } else {
-            self.current_token += 1;
-            self.lexer.next()
-        }
+        let (tok, span, slice) = match self.peeked.take() {
+            Some(peeked) => peeked,
+            None => self.lex()?,
+        };
+        self.prev_span = span;
+        self.prev_slice = slice;
+        self.consumed += 1;
+        Some(tok)
     }
 
     fn pop(&mut self) -> bool {
@@ -227,22 +963,41 @@ impl<'source> Parser<'source> {
     }
 
     fn next_or_error(&mut self) -> Result<Token, ParseError> {
-        Ok(self.next().ok_or(ParseError::ExpectedToken)??)
+        let eof_span = self.peek_span();
+        let tok = self.next().ok_or(ParseErrorKind::ExpectedToken.at(eof_span))?;
+        tok.map_err(|()| ParseErrorKind::ErrorToken.at(self.prev_span))
     }
 
-    fn peek(&mut self) -> &Option<Result<Token, ()>> {
+    fn peek(&mut self) -> Option<Result<Token, ()>> {
         if self.peeked.is_none() {
-            self.peeked = self.next()
+            self.peeked = self.lex();
         }
-        &self.peeked
+        self.peeked.as_ref().map(|(tok, _, _)| *tok)
     }
 
     fn peek_or_error(&mut self) -> Result<Token, ParseError> {
-        Ok(self.peek().ok_or(ParseError::ExpectedToken)??)
+        let span = self.peek_span();
+        let tok = self.peek().ok_or(ParseErrorKind::ExpectedToken.at(span))?;
+        tok.map_err(|()| ParseErrorKind::ErrorToken.at(span))
+    }
+
+    /// The span of the next token, or an empty span at the end of the source on EOF.
+    fn peek_span(&mut self) -> Span {
+        self.peek();
+        match &self.peeked {
+            Some((_, span, _)) => *span,
+            None => Span::point(self.lexer.source().len()),
+        }
+    }
+
+    /// The slice of the last consumed token.
+    fn slice(&self) -> &'source str {
+        self.prev_slice
     }
 
-    fn slice(&mut self) -> &'source str {
-        self.lexer.slice()
+    /// The span from `start` up to the end of the last consumed token.
+    fn span_from(&self, start: usize) -> Span {
+        Span::new(start, self.prev_span.end.max(start))
     }
 
     fn expect_next_token_to_be(&mut self, expected: Token) -> Result<(), ParseError> {
@@ -250,315 +1005,1140 @@ impl<'source> Parser<'source> {
         if next_tok == expected {
             Ok(())
         } else {
-            Err(ParseError::ExpectedDifferentToken { expected, found: next_tok })
+            Err(ParseErrorKind::ExpectedDifferentToken { expected, found: next_tok }.at(self.peek_span()))
         }
     }
 
-    pub fn parse_module(&mut self) -> Result<Module, ParseError> {
-        let mut decls = Vec::new();
-        while self.peek().is_some() {
-            decls.push(self.parse_group_member_statement()?);
+    /// Consumes the next token if it is `expected`, leaving it in place otherwise.
+    fn expect_token(&mut self, expected: Token) -> Result<(), ParseError> {
+        self.expect_next_token_to_be(expected)?;
+        self.pop();
+
+        Ok(())
+    }
+
+    /// Expects the `expected` token closing the delimiter at `opened`.
+    fn expect_closing(&mut self, expected: Token, opened: Span) -> Result<(), ParseError> {
+        if self.peek().is_none() {
+            return Err(ParseErrorKind::MissingTerminatingToken { expected, opened }.at(self.peek_span()));
         }
 
-        Ok(Module { decls })
+        self.expect_token(expected)
     }
 
-    pub fn parse_fun_decl(&mut self) -> Result<FunDeclStatement, ParseError> {
-        let fun_tok = self.next().ok_or(ParseError::ExpectedToken)??;
-        if fun_tok != Token::Fun {
-            return Err(ParseError::ExpectedDifferentToken {
-                expected: Token::Fun,
-                found: fun_tok,
-            });
+    /// Expects the `>` closing a generic argument list, splitting a `>>` into two `>`s so
+    /// nested lists like `List<List<Int>>` can be closed.
+    fn expect_closing_angle(&mut self) -> Result<(), ParseError> {
+        if self.peek() == Some(Ok(Token::ShiftRight))
+            && let Some((tok, span, slice)) = &mut self.peeked
+        {
+            *tok = Ok(Token::GreaterThan);
+            *span = Span::new(span.start + 1, span.end);
+            *slice = &slice[1..];
+            self.prev_span = Span::new(span.start - 1, span.start);
+            self.prev_slice = ">";
+            self.consumed += 1;
+            return Ok(());
         }
 
-        let name_tok = self.peek_or_error()?;
+        self.expect_token(Token::GreaterThan)
+    }
+
+    fn expect_ident(&mut self) -> Result<&'source str, ParseError> {
+        self.expect_token(Token::Ident)?;
+        Ok(self.slice())
+    }
+
+    /// Takes the errors recovered from so far, e.g. inside code blocks.
+    pub fn take_errors(&mut self) -> Vec<ParseError> {
+        std::mem::take(&mut self.errors)
+    }
+
+    /// Returns `true` if `tok` can only start a declaration or a statement.
+    fn is_sync_token(tok: Token) -> bool {
+        matches!(
+            tok,
+            Token::Fun
+                | Token::Class
+                | Token::Interface
+                | Token::Trait
+                | Token::Val
+                | Token::Let
+                | Token::Var
+                | Token::Ret
+                | Token::If
+                | Token::While
+                | Token::For
+                | Token::Break
+                | Token::Continue
+                | Token::At
+                | Token::Pub
+                | Token::Priv
+                | Token::Import
+                | Token::Group
+        )
+    }
+
+    /// Skips tokens until a statement or declaration boundary, so parsing can resume after
+    /// an error. A `;` is consumed, a `}` closing the current block or a token starting a new
+    /// declaration is left in place. Always makes progress past the token at `consumed_before`.
+    fn synchronize(&mut self, consumed_before: usize) {
+        let mut depth = 0usize;
+        while let Some(tok) = self.peek() {
+            let made_progress = self.consumed > consumed_before;
+            match tok {
+                Ok(Token::Semicolon) if depth == 0 => {
+                    self.pop();
+                    return;
+                }
+                Ok(Token::RightBrace) if depth == 0 && made_progress => return,
+                Ok(tok) if depth == 0 && made_progress && Self::is_sync_token(tok) => return,
+                Ok(Token::LeftBrace) => depth += 1,
+                Ok(Token::RightBrace) => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            self.pop();
+        }
+    }
+
+    /// Parses a whole module, recovering from errors at declaration boundaries. Returns the
+    /// declarations that could be parsed along with every error encountered.
+    pub fn parse_module(&mut self) -> (Module, Vec<ParseError>) {
+        let mut group: Option<GroupDecl> = None;
+        let mut imports = Vec::new();
+        let mut decls = Vec::new();
+        while let Some(tok) = self.peek() {
+            let consumed_before = self.consumed;
+            let result = match tok {
+                Ok(Token::Import) => self.parse_import_decl().map(|import| imports.push(import)),
+                Ok(Token::Group) => self.parse_group_decl().and_then(|decl| match &group {
+                    Some(first) => Err(ParseErrorKind::DuplicateGroupDecl { first: first.span }.at(decl.span)),
+                    None => {
+                        group = Some(decl);
+                        Ok(())
+                    }
+                }),
+                _ => self.parse_group_member_statement().map(|decl| decls.push(decl)),
+            };
+            if let Err(err) = result {
+                self.errors.push(err);
+                self.synchronize(consumed_before);
+            }
+        }
+
+        let module = Module {
+            group,
+            imports,
+            decls,
+            span: Span::new(0, self.lexer.source().len()),
+        };
+        (module, self.take_errors())
+    }
+
+    // <name>[.<name>...]
+    fn parse_group_path(&mut self) -> Result<Vec<String>, ParseError> {
+        let mut path = vec![self.expect_ident()?.to_string()];
+        while self.peek() == Some(Ok(Token::Dot)) {
+            self.pop();
+            path.push(self.expect_ident()?.to_string());
+        }
+        Ok(path)
+    }
+
+    // group <path>;
+    pub fn parse_group_decl(&mut self) -> Result<GroupDecl, ParseError> {
+        self.expect_token(Token::Group)?;
+        let start = self.prev_span.start;
+        let path = self.parse_group_path()?;
+        self.expect_token(Token::Semicolon)?;
+
+        Ok(GroupDecl {
+            path,
+            span: self.span_from(start),
+        })
+    }
+
+    // import <path>[.*| as <name>];
+    pub fn parse_import_decl(&mut self) -> Result<ImportDecl, ParseError> {
+        self.expect_token(Token::Import)?;
+        let start = self.prev_span.start;
+        let mut path = vec![self.expect_ident()?.to_string()];
+        let mut kind = ImportKind::Named { alias: None };
+        while self.peek() == Some(Ok(Token::Dot)) {
+            self.pop();
+            if self.peek() == Some(Ok(Token::Star)) {
+                self.pop();
+                kind = ImportKind::Glob;
+                break;
+            }
+            path.push(self.expect_ident()?.to_string());
+        }
+
+        if matches!(kind, ImportKind::Named { .. }) && self.peek() == Some(Ok(Token::As)) {
+            self.pop();
+            kind = ImportKind::Named {
+                alias: Some(self.expect_ident()?.to_string()),
+            };
+        }
+        self.expect_token(Token::Semicolon)?;
+
+        Ok(ImportDecl {
+            path,
+            kind,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parses the attributes and visibility that may precede any declaration.
+    fn parse_decl_header(&mut self) -> Result<DeclHeader, ParseError> {
+        let start = self.peek_span().start;
+        let attributes = self.parse_attribute_annots()?;
+        let visibility = self.parse_visibility_annot()?;
+
+        Ok(DeclHeader {
+            attributes,
+            visibility,
+            start,
+        })
+    }
+
+    pub fn parse_fun_decl(&mut self) -> Result<FunDeclStatement, ParseError> {
+        let header = self.parse_decl_header()?;
+        self.parse_fun_decl_with_header(header)
+    }
+
+    // fun[<n>][<typeParams>](<args>)[(:|->)<T>]
+    fn parse_fun_signature_with_header(&mut self, header: DeclHeader) -> Result<FunSignature, ParseError> {
+        self.expect_token(Token::Fun)?;
+
+        let name_tok = self.peek_or_error()?;
         let name = if name_tok == Token::Ident {
-            let name_slice = self.slice();
             self.pop();
-            Some(name_slice.to_string())
+            Some(self.slice().to_string())
         } else {
             None
         };
 
-        let args = self.parse_args_in_decl()?;
+        let type_params = self.parse_type_params()?;
 
-        let type_annot = self.parse_type_annot()?;
+        let args = self.parse_args_in_decl()?;
 
-        let code = self.parse_code_block()?.ok_or(ParseError::MissingCodeBlock)?;
+        let ret_type = self.parse_ret_type_annot()?;
 
-        Ok(FunDeclStatement {
-            attributes: vec![],
+        Ok(FunSignature {
+            attributes: header.attributes,
+            visibility: header.visibility,
             name,
-            visibility: VisibilityAnnot::Default,
-            ret_type: type_annot,
+            type_params,
+            ret_type,
             args,
+            span: self.span_from(header.start),
+        })
+    }
+
+    fn parse_fun_decl_with_header(&mut self, header: DeclHeader) -> Result<FunDeclStatement, ParseError> {
+        let start = header.start;
+        let signature = self.parse_fun_signature_with_header(header)?;
+
+        let code = self.parse_fun_body()?;
+
+        Ok(FunDeclStatement {
+            attributes: signature.attributes,
+            name: signature.name,
+            type_params: signature.type_params,
+            visibility: signature.visibility,
+            ret_type: signature.ret_type,
+            args: signature.args,
             code,
+            span: self.span_from(start),
         })
     }
 
+    // (<codeBlock>|=><v>)
+    fn parse_fun_body(&mut self) -> Result<CodeBlock, ParseError> {
+        if self.peek() == Some(Ok(Token::FatArrow)) {
+            let start = self.peek_span().start;
+            self.pop();
+            let value = self.parse_inner_expr()?;
+
+            return Ok(vec![RuntimeStatement::Return {
+                value: Some(value),
+                span: self.span_from(start),
+            }]);
+        }
+
+        self.expect_code_block()
+    }
+
+    fn expect_code_block(&mut self) -> Result<CodeBlock, ParseError> {
+        self.expect_block()?.into_code()
+    }
+
+    /// Functions with an arrow body may be followed by a `;`, which is skipped.
+    fn skip_optional_semicolon(&mut self) {
+        if self.peek() == Some(Ok(Token::Semicolon)) {
+            self.pop();
+        }
+    }
+
     // =<v>
     pub fn parse_assignment(&mut self) -> Result<Option<Expr>, ParseError> {
-        if let Some(equals_tok_res) = self.peek() {
-            let equals_tok = (*equals_tok_res)?;
-            if equals_tok == Token::Equals {
-                self.pop();
-                let expr = self.parse_expr()?;
-                return Ok(Some(expr));
-            }
+        if self.peek().is_some() && self.peek_or_error()? == Token::Equals {
+            self.pop();
+            let expr = self.parse_inner_expr()?;
+            return Ok(Some(expr));
         }
 
         Ok(None)
     }
 
-    // val<n>[:<T>][=<v>]
+    // (val|let)<n>[:<T>][=<v>]
     pub fn parse_immutable_variable_decl(&mut self) -> Result<ValDeclStatement, ParseError> {
-        let val_tok = self.next().ok_or(ParseError::ExpectedToken)??;
-        if val_tok != Token::Val {
-            return Err(ParseError::ExpectedDifferentToken {
-                expected: Token::Val,
+        let header = self.parse_decl_header()?;
+        self.parse_immutable_variable_decl_with_header(header)
+    }
+
+    fn parse_immutable_variable_decl_with_header(
+        &mut self,
+        header: DeclHeader,
+    ) -> Result<ValDeclStatement, ParseError> {
+        let val_tok = self.next_or_error()?;
+        if val_tok != Token::Val && val_tok != Token::Let {
+            return Err(ParseErrorKind::ExpectedDifferentTokens {
+                expected: vec![Token::Val, Token::Let],
                 found: val_tok,
-            });
+            }
+            .at(self.prev_span));
         }
 
-        let name_tok = self.next_or_error()?;
-        if name_tok != Token::Ident {
-            return Err(ParseError::ExpectedDifferentToken { expected: Token::Ident, found: name_tok }   )
-        }
-        let name_slice = self.slice();
+        let name_slice = self.expect_ident()?;
 
         let type_annot = self.parse_type_annot()?;
 
         let initial_assignment = self.parse_assignment()?;
 
-        Ok(ValDeclStatement{
-            attributes: vec![], // TODO: Add attributes support
-            visibility: VisibilityAnnot::Default, // TODO: Add visibility support
+        Ok(ValDeclStatement {
+            attributes: header.attributes,
+            visibility: header.visibility,
             name: name_slice.to_string(),
             type_annot,
             initial_assignment,
+            span: self.span_from(header.start),
         })
     }
 
-    // var<n>[:<T>][=<v>]
+    // var<n>[:<T>]=<v>
     pub fn parse_var_decl(&mut self) -> Result<VarDeclStatement, ParseError> {
-        let attributes = self.parse_attribute_annots()?;
+        let header = self.parse_decl_header()?;
+        self.parse_var_decl_with_header(header)
+    }
 
-        let var_tok = self.next().ok_or(ParseError::ExpectedToken)??;
-        if var_tok != Token::Var {
-            return Err(ParseError::ExpectedDifferentToken {
-                expected: Token::Var,
-                found: var_tok,
-            });
-        }
+    fn parse_var_decl_with_header(&mut self, header: DeclHeader) -> Result<VarDeclStatement, ParseError> {
+        self.expect_token(Token::Var)?;
 
-        let name_tok = self.next_or_error()?;
-        if name_tok != Token::Ident {
-            return Err(ParseError::ExpectedDifferentToken { expected: Token::Ident, found: name_tok })
-        }
-        let name_slice = self.slice();
+        let name_slice = self.expect_ident()?;
 
-        let type_annot = self.parse_type_annot()?.ok_or(ParseError::MissingTypeAnnot)?;
+        let type_annot = self.parse_type_annot()?;
 
-        let initial_assignment = self.parse_assignment()?.ok_or(ParseError::MissingAssignment)?;
+        let initial_assignment = self
+            .parse_assignment()?
+            .ok_or(ParseErrorKind::MissingAssignment.at(self.span_from(header.start)))?;
 
-        Ok(VarDeclStatement{
-            attributes,
-            visibility: VisibilityAnnot::Default, // TODO: Add visibility support
+        Ok(VarDeclStatement {
+            attributes: header.attributes,
+            visibility: header.visibility,
             name: name_slice.to_string(),
             type_annot,
             initial_assignment,
+            span: self.span_from(header.start),
         })
     }
 
+    // class[<n>][(:|->)<Parents...>]<declBlock>
+    pub fn parse_class_decl(&mut self) -> Result<ClassDeclStatement, ParseError> {
+        let header = self.parse_decl_header()?;
+        self.parse_class_decl_with_header(header)
+    }
+
+    fn parse_class_decl_with_header(&mut self, header: DeclHeader) -> Result<ClassDeclStatement, ParseError> {
+        self.expect_token(Token::Class)?;
+
+        let name = if self.peek() == Some(Ok(Token::Ident)) {
+            self.pop();
+            Some(self.slice().to_string())
+        } else {
+            None
+        };
+
+        let type_params = self.parse_type_params()?;
+
+        let parents = self.parse_parents()?;
+
+        let decls = self.parse_decl_block(Self::parse_class_member_statement)?;
+
+        Ok(ClassDeclStatement {
+            attributes: header.attributes,
+            visibility: header.visibility,
+            name,
+            type_params,
+            parents,
+            decls,
+            span: self.span_from(header.start),
+        })
+    }
+
+    // [(:|->)<Parents...>]
+    fn parse_parents(&mut self) -> Result<Vec<TypeExpr>, ParseError> {
+        let mut parents = Vec::new();
+        if matches!(self.peek(), Some(Ok(Token::Colon | Token::Arrow))) {
+            self.pop();
+            loop {
+                parents.push(self.parse_type_expr()?);
+                if self.peek() != Some(Ok(Token::Comma)) {
+                    break;
+                }
+                self.pop();
+            }
+        }
+
+        Ok(parents)
+    }
+
+    // [<<n>[:<T>[+<T>...]],...>]
+    fn parse_type_params(&mut self) -> Result<Vec<TypeParam>, ParseError> {
+        let mut params = Vec::new();
+        if self.peek() != Some(Ok(Token::LessThan)) {
+            return Ok(params);
+        }
+        self.pop();
+
+        loop {
+            let start = self.peek_span().start;
+            let name = self.expect_ident()?.to_string();
+
+            let mut bounds = Vec::new();
+            if self.peek() == Some(Ok(Token::Colon)) {
+                self.pop();
+                loop {
+                    bounds.push(self.parse_type_expr()?);
+                    if self.peek() != Some(Ok(Token::Plus)) {
+                        break;
+                    }
+                    self.pop();
+                }
+            }
+
+            params.push(TypeParam {
+                name,
+                bounds,
+                span: self.span_from(start),
+            });
+
+            if self.peek() != Some(Ok(Token::Comma)) {
+                break;
+            }
+            self.pop();
+        }
+        self.expect_closing_angle()?;
+
+        Ok(params)
+    }
+
+    /// Parses a `{ ... }` block of declarations with `parse_member`, recovering from errors in
+    /// individual members.
+    fn parse_decl_block<T>(
+        &mut self,
+        mut parse_member: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        self.expect_token(Token::LeftBrace)?;
+        let opened = self.prev_span;
+
+        let mut decls = Vec::new();
+        loop {
+            match self.peek() {
+                None => {
+                    return Err(ParseErrorKind::MissingTerminatingToken {
+                        expected: Token::RightBrace,
+                        opened,
+                    }
+                    .at(self.peek_span()));
+                }
+                Some(Ok(Token::RightBrace)) => break,
+                Some(_) => {}
+            }
+
+            let consumed_before = self.consumed;
+            match parse_member(self) {
+                Ok(decl) => decls.push(decl),
+                Err(err) => {
+                    self.errors.push(err);
+                    self.synchronize(consumed_before);
+                }
+            }
+        }
+
+        self.pop(); // Pop the terminating RightBrace
+
+        Ok(decls)
+    }
+
+    // (interface|trait)<n>[:<Parents...>]<declBlock>
+    pub fn parse_interface_decl(&mut self) -> Result<InterfaceDeclStatement, ParseError> {
+        let header = self.parse_decl_header()?;
+        self.parse_interface_decl_with_header(header)
+    }
+
+    fn parse_interface_decl_with_header(
+        &mut self,
+        header: DeclHeader,
+    ) -> Result<InterfaceDeclStatement, ParseError> {
+        let kind = match self.next_or_error()? {
+            Token::Interface => InterfaceKind::Interface,
+            Token::Trait => InterfaceKind::Trait,
+            t => {
+                return Err(ParseErrorKind::ExpectedDifferentTokens {
+                    expected: vec![Token::Interface, Token::Trait],
+                    found: t,
+                }
+                .at(self.prev_span));
+            }
+        };
+
+        let name = self.expect_ident()?.to_string();
+
+        let type_params = self.parse_type_params()?;
+
+        let parents = self.parse_parents()?;
+
+        let methods = self.parse_decl_block(Self::parse_fun_signature)?;
+
+        Ok(InterfaceDeclStatement {
+            attributes: header.attributes,
+            visibility: header.visibility,
+            kind,
+            name,
+            type_params,
+            parents,
+            methods,
+            span: self.span_from(header.start),
+        })
+    }
+
+    // fun[<n>](<args>)[:<T>];
+    pub fn parse_fun_signature(&mut self) -> Result<FunSignature, ParseError> {
+        let header = self.parse_decl_header()?;
+        let signature = self.parse_fun_signature_with_header(header)?;
+        self.expect_token(Token::Semicolon)?;
+
+        Ok(signature)
+    }
+
+    pub fn parse_class_member_statement(&mut self) -> Result<ClassMemberStatement, ParseError> {
+        let header = self.parse_decl_header()?;
+
+        match self.peek_or_error()? {
+            Token::Fun => {
+                let decl = self.parse_fun_decl_with_header(header)?;
+                self.skip_optional_semicolon();
+                Ok(ClassMemberStatement::Fun(decl))
+            }
+            Token::Val | Token::Let => {
+                let decl = self.parse_immutable_variable_decl_with_header(header)?;
+                self.expect_token(Token::Semicolon)?;
+                Ok(ClassMemberStatement::Let(decl))
+            }
+            Token::Var => {
+                let decl = self.parse_var_decl_with_header(header)?;
+                self.expect_token(Token::Semicolon)?;
+                Ok(ClassMemberStatement::Var(decl))
+            }
+            t => Err(ParseErrorKind::UnexpectedToken(t).at(self.peek_span())),
+        }
+    }
+
+    /// Parses the code block of a statement, which has no value, so a trailing expression
+    /// without `;` is discarded.
     pub fn parse_code_block(&mut self) -> Result<Option<Vec<RuntimeStatement>>, ParseError> {
-        if *self.peek() != Some(Ok(Token::LeftBrace)) {
+        self.parse_block()?.map(PendingBlock::into_code).transpose()
+    }
+
+    /// A `let` or `var` whose closure ends in a block, like `let f = fun() { ... }`, may omit its `;`.
+    fn ends_with_closure_block(&self, statement: &RuntimeStatement) -> bool {
+        let initial_assignment = match statement {
+            RuntimeStatement::Let(decl) => decl.initial_assignment.as_ref(),
+            RuntimeStatement::Var(decl) => Some(&decl.initial_assignment),
+            _ => None,
+        };
+        matches!(initial_assignment, Some(Expr { kind: ExprKind::Lambda(_), span })
+            if span.end == self.prev_span.end && self.prev_slice == "}")
+    }
+
+    fn expect_block(&mut self) -> Result<PendingBlock, ParseError> {
+        let code_span = self.peek_span();
+        self.parse_block()?
+            .ok_or(ParseErrorKind::MissingCodeBlock.at(code_span))
+    }
+
+    /// Parses `{ <statements> }`. An expression or an if statement right before the closing `}`
+    /// may omit its `;`, in which case it becomes the value of the block if that is used.
+    fn parse_block(&mut self) -> Result<Option<PendingBlock>, ParseError> {
+        if self.peek() != Some(Ok(Token::LeftBrace)) {
             return Ok(None);
         }
         self.pop();
+        let opened = self.prev_span;
+        let mut tail = None;
 
         let mut statements = Vec::new();
-        while self.peek_or_error()? != Token::RightBrace {
-            statements.push(self.parse_runtime_statement()?);
-            self.expect_next_token_to_be(Token::Semicolon)?;
-            self.pop();
+        loop {
+            if self.peek().is_none() {
+                return Err(ParseErrorKind::MissingTerminatingToken {
+                    expected: Token::RightBrace,
+                    opened,
+                }
+                .at(self.peek_span()));
+            }
+            if self.peek_or_error()? == Token::RightBrace {
+                break;
+            }
+
+            let consumed_before = self.consumed;
+            if self.peek() == Some(Ok(Token::If)) {
+                match self.parse_if_chain() {
+                    Ok(chain) if self.peek() == Some(Ok(Token::RightBrace)) => tail = Some(Tail::If(chain)),
+                    Ok(chain) => match chain.into_statement() {
+                        Ok(statement) => statements.push(RuntimeStatement::If(statement)),
+                        Err(err) => self.errors.push(err),
+                    },
+                    Err(err) => {
+                        self.errors.push(err);
+                        self.synchronize(consumed_before);
+                    }
+                }
+                continue;
+            }
+
+            let statement = self.parse_runtime_statement().and_then(|statement| {
+                let is_value = matches!(statement, RuntimeStatement::Discard(_))
+                    && self.peek() == Some(Ok(Token::RightBrace));
+                if self.ends_with_closure_block(&statement) {
+                    self.skip_optional_semicolon();
+                    return Ok((statement, true));
+                }
+                if statement.needs_semicolon() && !is_value {
+                    self.expect_token(Token::Semicolon)?;
+                    return Ok((statement, true));
+                }
+                Ok((statement, false))
+            });
+            match statement {
+                Ok((RuntimeStatement::Discard(expr), false)) => tail = Some(Tail::Expr(expr)),
+                Ok((statement, _)) => statements.push(statement),
+                Err(err) => {
+                    self.errors.push(err);
+                    self.synchronize(consumed_before);
+                }
+            }
         }
 
         self.pop(); // Pop the terminating RightBrace
 
-        Ok(Some(statements))
+        Ok(Some(PendingBlock {
+            code: statements,
+            tail,
+            span: Span::new(opened.start, self.prev_span.end),
+        }))
     }
 
     pub fn parse_attribute_annots(&mut self) -> Result<Vec<AttributeAnnot>, ParseError> {
         let mut annots = Vec::new();
-        loop {
-            let annot = self.parse_attribute_annot()?;
-            if annot.is_none() {
-                break;
-            }
-            annots.push(annot.unwrap());
+        while let Some(annot) = self.parse_attribute_annot()? {
+            annots.push(annot);
         }
         Ok(annots)
     }
 
     pub fn parse_attribute_annot(&mut self) -> Result<Option<AttributeAnnot>, ParseError> {
-        if *self.peek() != Some(Ok(Token::At)) {
+        if self.peek() != Some(Ok(Token::At)) {
             return Ok(None);
         }
+        let start = self.peek_span().start;
         self.pop();
 
-        let name_tok = self.next_or_error()?;
-        if name_tok != Token::Ident {
-            return Err(ParseError::ExpectedDifferentToken {
-                expected: Token::Ident,
-                found: name_tok,
-            });
-        }
-        let name_slice = self.slice();
+        let name_slice = self.expect_ident()?;
 
-        let left_paren_tok_opt = self.peek();
-        let args = if left_paren_tok_opt.is_some() && left_paren_tok_opt.unwrap()? == Token::LeftParen {
+        let args = if self.peek() == Some(Ok(Token::LeftParen)) {
             self.parse_args_in_call()?
         } else {
             vec![]
         };
 
-        Ok(Some(AttributeAnnot { name: name_slice.to_string(), args}))
+        Ok(Some(AttributeAnnot {
+            name: name_slice.to_string(),
+            args,
+            span: self.span_from(start),
+        }))
     }
 
     pub fn parse_visibility_annot(&mut self) -> Result<VisibilityAnnot, ParseError> {
-        let visibility_tok = self.peek_or_error()?;
-        let visibility = match visibility_tok {
+        let visibility = match self.peek_or_error()? {
             Token::Pub => VisibilityAnnot::Public,
             Token::Priv => VisibilityAnnot::Private,
-            _ => VisibilityAnnot::Default,
+            _ => return Ok(VisibilityAnnot::Default),
         };
         self.next();
 
         Ok(visibility)
     }
 
-    pub fn parse_type_annot(&mut self) -> Result<Option<String>, ParseError> {
-        let colon_tok = self.peek_or_error()?;
-        if colon_tok != Token::Colon {
+    pub fn parse_type_annot(&mut self) -> Result<Option<TypeExpr>, ParseError> {
+        if self.peek() != Some(Ok(Token::Colon)) {
             return Ok(None);
         }
 
         self.next();
 
-        let type_tok = self.next().ok_or(ParseError::ExpectedToken)??;
-        if type_tok != Token::Ident {
-            return Err(ParseError::ExpectedDifferentToken {
-                expected: Token::Ident,
-                found: type_tok,
-            });
-        }
+        Ok(Some(self.parse_type_expr()?))
+    }
 
-        let type_slice = self.slice();
+    /// Parses a return type annotation, introduced by either `:` or `->`.
+    fn parse_ret_type_annot(&mut self) -> Result<Option<TypeExpr>, ParseError> {
+        if self.peek() != Some(Ok(Token::Arrow)) {
+            return self.parse_type_annot();
+        }
 
-        Ok(Some(type_slice.to_string()))
+        self.pop();
+        Ok(Some(self.parse_type_expr()?))
     }
 
     pub fn parse_group_member_statement(&mut self) -> Result<GroupMemberStatement, ParseError> {
-        let type_tok = self.peek_or_error()?;
-        
-        match type_tok {
-            Token::Fun => Ok(GroupMemberStatement::Fun(self.parse_fun_decl()?)),
-            Token::Val => todo!(),
-            Token::Class => todo!(),
-            t => Err(ParseError::UnexpectedToken(t)),
+        let header = self.parse_decl_header()?;
+
+        match self.peek_or_error()? {
+            Token::Fun => {
+                let decl = self.parse_fun_decl_with_header(header)?;
+                self.skip_optional_semicolon();
+                Ok(GroupMemberStatement::Fun(decl))
+            }
+            Token::Class => Ok(GroupMemberStatement::Class(self.parse_class_decl_with_header(header)?)),
+            Token::Interface | Token::Trait => Ok(GroupMemberStatement::Interface(
+                self.parse_interface_decl_with_header(header)?,
+            )),
+            Token::Val | Token::Let => {
+                let decl = self.parse_immutable_variable_decl_with_header(header)?;
+                self.expect_token(Token::Semicolon)?;
+                Ok(GroupMemberStatement::Let(decl))
+            }
+            t => Err(ParseErrorKind::UnexpectedToken(t).at(self.peek_span())),
         }
     }
 
     pub fn parse_runtime_statement(&mut self) -> Result<RuntimeStatement, ParseError> {
         let type_tok = self.peek_or_error()?;
-        
-        match type_tok {
-            Token::Val => todo!(),
-            t => Err(ParseError::UnexpectedToken(t)),
+        if !matches!(type_tok, Token::At | Token::Val | Token::Let | Token::Var) {
+            return match type_tok {
+                Token::Ret => self.parse_return_statement(),
+                Token::If => Ok(RuntimeStatement::If(self.parse_if_statement()?)),
+                Token::While => Ok(RuntimeStatement::While(self.parse_while_statement()?)),
+                Token::For => self.parse_for_statement(),
+                Token::Break => {
+                    self.pop();
+                    Ok(RuntimeStatement::Break(self.prev_span))
+                }
+                Token::Continue => {
+                    self.pop();
+                    Ok(RuntimeStatement::Continue(self.prev_span))
+                }
+                _ => Ok(RuntimeStatement::Discard(self.parse_inner_expr()?)),
+            };
         }
+
+        let header = DeclHeader {
+            start: self.peek_span().start,
+            attributes: self.parse_attribute_annots()?,
+            visibility: VisibilityAnnot::Default,
+        };
+
+        match self.peek_or_error()? {
+            Token::Val | Token::Let => Ok(RuntimeStatement::Let(
+                self.parse_immutable_variable_decl_with_header(header)?,
+            )),
+            Token::Var => Ok(RuntimeStatement::Var(self.parse_var_decl_with_header(header)?)),
+            t => Err(ParseErrorKind::UnexpectedToken(t).at(self.peek_span())),
+        }
+    }
+
+    // ret[<v>]
+    fn parse_return_statement(&mut self) -> Result<RuntimeStatement, ParseError> {
+        let start = self.peek_span().start;
+        self.expect_token(Token::Ret)?;
+
+        let value = match self.peek() {
+            None | Some(Ok(Token::Semicolon | Token::RightBrace)) => None,
+            Some(_) => Some(self.parse_inner_expr()?),
+        };
+
+        Ok(RuntimeStatement::Return {
+            value,
+            span: self.span_from(start),
+        })
+    }
+
+    // if<cond><codeBlock>[else if<cond><codeBlock>...][else<codeBlock>]
+    pub fn parse_if_statement(&mut self) -> Result<IfStatement, ParseError> {
+        self.parse_if_chain()?.into_statement()
+    }
+
+    fn parse_if_chain(&mut self) -> Result<IfChain, ParseError> {
+        let start = self.peek_span().start;
+        let mut arms = Vec::new();
+        let mut else_arm = None;
+
+        loop {
+            let arm_start = self.peek_span().start;
+            self.expect_token(Token::If)?;
+            let cond = self.parse_inner_expr()?;
+
+            if self.peek() != Some(Ok(Token::LeftBrace)) {
+                // Without braces, the rest of the chain can only be an if expression
+                let expected = self.peek_span();
+                let if_expr = self.parse_if_expr_arms(cond, arm_start).map_err(|err| match err.kind {
+                    ParseErrorKind::IfExprWithoutElse => ParseErrorKind::MissingCodeBlock.at(expected),
+                    _ => err,
+                })?;
+                let value = Expr::new(ExprKind::If(if_expr), self.span_from(arm_start));
+                else_arm = Some(ElseArm::Braceless(value, expected));
+                break;
+            }
+            arms.push((cond, self.expect_block()?, arm_start));
+
+            if self.peek() != Some(Ok(Token::Else)) {
+                break;
+            }
+            self.pop();
+
+            if self.peek() != Some(Ok(Token::If)) {
+                else_arm = Some(ElseArm::Block(Box::new(self.expect_block()?)));
+                break;
+            }
+        }
+
+        Ok(IfChain {
+            arms,
+            else_arm,
+            span: self.span_from(start),
+        })
+    }
+
+    // while<cond><codeBlock>
+    pub fn parse_while_statement(&mut self) -> Result<WhileStatement, ParseError> {
+        let start = self.peek_span().start;
+        self.expect_token(Token::While)?;
+        let cond = self.parse_inner_expr()?;
+        let code = self.expect_code_block()?;
+
+        Ok(WhileStatement {
+            cond,
+            code,
+            span: self.span_from(start),
+        })
+    }
+
+    // for<n>in<v><codeBlock> or for<cond><codeBlock>
+    fn parse_for_statement(&mut self) -> Result<RuntimeStatement, ParseError> {
+        let start = self.peek_span().start;
+        self.expect_token(Token::For)?;
+        let head = self.parse_inner_expr()?;
+
+        if self.peek() != Some(Ok(Token::In)) {
+            // `for <cond>` is a while loop
+            let code = self.expect_code_block()?;
+            return Ok(RuntimeStatement::While(WhileStatement {
+                cond: head,
+                code,
+                span: self.span_from(start),
+            }));
+        }
+
+        let ExprKind::Read(iterator) = head.kind else {
+            return Err(ParseErrorKind::InvalidLoopVariable.at(head.span));
+        };
+        self.pop(); // Pop the `in`
+
+        let iterable = self.parse_inner_expr()?;
+        let code = self.expect_code_block()?;
+
+        Ok(RuntimeStatement::For(ForStatement {
+            iterator,
+            iterable,
+            code,
+            span: self.span_from(start),
+        }))
     }
 
+    /// Parses a single expression. Errors recovered from inside its blocks fail the whole parse,
+    /// with the first of them returned.
     pub fn parse_expr(&mut self) -> Result<Expr, ParseError> {
-        self.parse_binary_expr(0)
+        let expr = self.parse_inner_expr()?;
+        match self.take_errors().into_iter().next() {
+            Some(err) => Err(err),
+            None => Ok(expr),
+        }
+    }
+
+    /// Parses an expression nested in a larger construct, leaving recovered errors to its caller.
+    fn parse_inner_expr(&mut self) -> Result<Expr, ParseError> {
+        self.parse_assign_expr()
+    }
+
+    // <lvalue>(=|+=|-=|...)<v>, right-associative
+    fn parse_assign_expr(&mut self) -> Result<Expr, ParseError> {
+        let target = self.parse_binary_expr(0)?;
+
+        let op = match self.peek() {
+            Some(Ok(Token::Equals)) => None,
+            Some(Ok(tok)) => match BinOp::from_compound_assign_token(tok) {
+                Some(op) => Some(op),
+                None => return Ok(target),
+            },
+            _ => return Ok(target),
+        };
+
+        if !target.is_assignable() {
+            return Err(ParseErrorKind::NotAssignable.at(target.span));
+        }
+        self.pop();
+
+        let value = self.parse_assign_expr()?;
+        let span = target.span.to(value.span);
+        Ok(Expr::new(
+            ExprKind::Assign {
+                target: Box::new(target),
+                op,
+                value: Box::new(value),
+            },
+            span,
+        ))
+    }
+
+    /// Parses an `IntLiteral` or `UIntLiteral`. Hex, binary and octal literals are bit patterns
+    /// and may use the whole unsigned range of a signed type, e.g. `0xFFi8` is `-1`. A `negated`
+    /// literal has its `-` folded in, so a decimal one may reach its type's minimum, as in `-128i8`.
+    fn parse_int_literal(&self, slice: &str, negated: bool) -> Result<ExprKind, ParseError> {
+        let num_str = slice.replace('_', "");
+        let (radix, digits) = match num_str.get(..2) {
+            Some("0x") => (16, &num_str[2..]),
+            Some("0b") => (2, &num_str[2..]),
+            Some("0o") => (8, &num_str[2..]),
+            _ => (10, num_str.as_str()),
+        };
+        let (digits, suffix) = digits.split_at(digits.find(['i', 'u']).unwrap_or(digits.len()));
+
+        let unsigned = suffix.starts_with('u');
+        let suffix = NumSuffix::from_suffix(suffix);
+        let ty = suffix.unwrap_or(if unsigned { NumSuffix::U64 } else { NumSuffix::I64 });
+        let out_of_range = || ParseErrorKind::LiteralOutOfRange(ty).at(self.prev_span);
+
+        let magnitude = u64::from_str_radix(digits, radix).map_err(|err: ParseIntError| match err.kind() {
+            IntErrorKind::PosOverflow => out_of_range(),
+            _ => ParseErrorKind::from(err).at(self.prev_span),
+        })?;
+
+        let bits = ty.bits();
+        let max = match (unsigned || radix != 10, negated) {
+            (true, _) => u64::MAX >> (64 - bits),
+            (false, true) => 1 << (bits - 1),
+            (false, false) => (1 << (bits - 1)) - 1,
+        };
+        if magnitude > max {
+            return Err(out_of_range());
+        }
+
+        let literal = if unsigned {
+            LiteralExpr::UInt(magnitude, suffix)
+        } else {
+            // Sign-extends the bit pattern to the literal's width, e.g. `0x80i8` to `-128`
+            let shift = 64 - bits;
+            let value = ((magnitude << shift) as i64) >> shift;
+            // Only decimal literals are negated. Their magnitude is at most the minimum's, which
+            // `wrapping_neg` maps onto itself
+            let value = if negated { (magnitude as i64).wrapping_neg() } else { value };
+            LiteralExpr::Int(value, suffix)
+        };
+
+        Ok(ExprKind::Literal(literal))
     }
 
-    fn parse_int_literal(&self, slice: &str) -> Result<Expr, ParseError> {
-        let num_str = slice.replace("_", "");
-        let num: i64 = num_str.parse()?;
+    fn parse_float_literal(&self, slice: &str) -> Result<ExprKind, ParseError> {
+        let num_str = slice.replace('_', "");
+        let (num_str, suffix) = match num_str.len().checked_sub(3).map(|idx| num_str.split_at(idx)) {
+            Some((num, suffix)) if suffix.starts_with('f') => (num, NumSuffix::from_suffix(suffix)),
+            _ => (num_str.as_str(), None),
+        };
+
+        let value: f64 = num_str
+            .parse()
+            .map_err(|err: ParseFloatError| ParseErrorKind::from(err).at(self.prev_span))?;
 
-        Ok(Expr::Literal(LiteralExpr::Int(num)))
+        let ty = suffix.unwrap_or(NumSuffix::F64);
+        let in_range = match ty {
+            NumSuffix::F32 => (value as f32).is_finite(),
+            _ => value.is_finite(),
+        };
+        if !in_range {
+            return Err(ParseErrorKind::LiteralOutOfRange(ty).at(self.prev_span));
+        }
+
+        Ok(ExprKind::Literal(LiteralExpr::Float(value, suffix)))
     }
 
-    fn parse_uint_literal(&self, slice: &str) -> Result<Expr, ParseError> {
-        let mut num_str = slice.to_string();
-        num_str.pop();
-        num_str = num_str.replace("_", "");
-        let num: u64 = num_str.parse()?;
+    fn parse_str_literal(&mut self, slice: &str) -> Result<ExprKind, ParseError> {
+        let body = &slice[1..slice.len() - 1];
+        let body_start = self.prev_span.start + 1;
+        let parts = str_parts(slice);
+
+        if !parts.iter().any(|part| matches!(part, StrPart::Interpolation(_))) {
+            return Ok(ExprKind::Literal(LiteralExpr::Str(unescape(body, body_start)?)));
+        }
 
-        Ok(Expr::Literal(LiteralExpr::UInt(num)))
+        let parts = parts
+            .into_iter()
+            .map(|part| match part {
+                StrPart::Text(range) => {
+                    unescape(&body[range.clone()], body_start + range.start).map(InterpolatedPart::Str)
+                }
+                StrPart::Interpolation(range) => self
+                    .parse_interpolation(body_start + range.start, body_start + range.end)
+                    .map(InterpolatedPart::Expr),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(ExprKind::Interpolated(parts))
     }
 
-    fn parse_float_literal(&self, slice: &str) -> Result<Expr, ParseError> {
-        let num_str = slice.replace("_", "");
+    /// Parses the expression between the braces of an interpolation spanning `start..end`. It's
+    /// parsed from the original source so its spans point into the string literal.
+    fn parse_interpolation(&mut self, start: usize, end: usize) -> Result<Expr, ParseError> {
+        let mut parser = Parser::new(&self.lexer.source()[..end]);
+        parser.lexer.bump(start);
 
-        let value: f64 = num_str.parse()?;
+        if parser.peek().is_none() {
+            return Err(ParseErrorKind::EmptyInterpolation.at(Span::new(start - 1, end + 1)));
+        }
 
-        Ok(Expr::Literal(LiteralExpr::Float(value)))
+        let expr = parser.parse_inner_expr()?;
+        if parser.peek().is_some() {
+            let span = parser.peek_span();
+            return Err(match parser.peek_or_error() {
+                Ok(tok) => ParseErrorKind::UnexpectedToken(tok).at(span),
+                Err(err) => err,
+            });
+        }
+
+        self.errors.extend(parser.take_errors());
+        Ok(expr)
     }
 
-    fn parse_str_literal(&self, slice: &str) -> Result<Expr, ParseError> {
-        let value = &slice[1..slice.len() - 1];
+    /// Raw strings are taken verbatim, only the `r`, the hashes and the quotes are stripped.
+    fn parse_raw_str_literal(&self, slice: &str) -> ExprKind {
+        let hashes = slice[1..].find('"').unwrap_or(0);
+        let value = &slice[hashes + 2..slice.len() - hashes - 1];
 
-        Ok(Expr::Literal(LiteralExpr::Str(value.to_string())))
+        ExprKind::Literal(LiteralExpr::Str(value.to_string()))
+    }
+
+    fn parse_char_literal(&self, slice: &str) -> Result<ExprKind, ParseError> {
+        let value = unescape(&slice[1..slice.len() - 1], self.prev_span.start + 1)?;
+
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(ExprKind::Literal(LiteralExpr::Char(c))),
+            (None, _) => Err(ParseErrorKind::EmptyCharLiteral.at(self.prev_span)),
+            (Some(_), Some(_)) => Err(ParseErrorKind::MultipleCharsInCharLiteral.at(self.prev_span)),
+        }
     }
 
     fn parse_binary_expr(&mut self, min_prec: u8) -> Result<Expr, ParseError> {
-        let mut left = self.parse_primary_expr()?;
+        let mut left = self.parse_unary_expr()?;
+        let mut non_assoc_prec = None;
 
         while let Some(Ok(op_token)) = self.peek() {
-            let (op, prec) = match op_token {
-                Token::Plus => (BinOp::Add, 4),
-                Token::Minus => (BinOp::Sub, 4),
-                Token::Star => (BinOp::Mul, 5),
-                Token::Slash => (BinOp::Div, 5),
-                Token::Percent => (BinOp::Mod, 5),
-
-                Token::EqualsEquals => (BinOp::Equals, 2),
-                Token::NotEquals => (BinOp::NotEquals, 2),
-                Token::GreaterThanEquals => (BinOp::GreaterEqual, 2),
-                Token::LessThanEquals => (BinOp::LowerEqual, 2),
-                Token::GreaterThan => (BinOp::Greater, 3),
-                Token::LessThan => (BinOp::Lower, 3),
-                Token::Equals => (BinOp::Assign, 1),
-                _ => break,
+            let Some(op) = BinOp::from_token(op_token) else {
+                break;
             };
 
+            let prec = op.precedence();
             if prec < min_prec {
                 break;
             }
 
+            let assoc = op.associativity();
+            if assoc == Assoc::None && non_assoc_prec == Some(prec) {
+                return Err(ParseErrorKind::NonAssociativeOperator(op_token).at(self.peek_span()));
+            }
+
             self.next();
 
             let right = self.parse_binary_expr(prec + 1)?;
-            left = Expr::Binary {
-                left: Box::new(left),
-                op,
-                right: Box::new(right),
-            };
+            let span = left.span.to(right.span);
+            left = Expr::new(
+                ExprKind::Binary {
+                    left: Box::new(left),
+                    op,
+                    right: Box::new(right),
+                },
+                span,
+            );
+
+            if assoc == Assoc::None {
+                non_assoc_prec = Some(prec);
+            }
         }
 
         Ok(left)
     }
 
+    // [!|-|+|~]...<v>
+    fn parse_unary_expr(&mut self) -> Result<Expr, ParseError> {
+        let op = match self.peek() {
+            Some(Ok(Token::Not)) => UnaryOp::Not,
+            Some(Ok(Token::Minus)) => UnaryOp::Negative,
+            Some(Ok(Token::Plus)) => UnaryOp::Positive,
+            Some(Ok(Token::Tilde)) => UnaryOp::BitNot,
+            _ => return self.parse_postfix_expr(),
+        };
+        let start = self.peek_span().start;
+        self.pop();
+
+        let val = if op == UnaryOp::Negative && self.peek() == Some(Ok(Token::IntLiteral)) {
+            self.pop();
+            let slice = self.slice();
+            // Only signed decimal literals are folded. A bit pattern like `-0xFFi8` stays a negation.
+            let is_signed_decimal = !matches!(slice.get(..2), Some("0x" | "0b" | "0o")) && !slice.contains('u');
+            let has_postfix = matches!(self.peek(), Some(Ok(Token::LeftParen | Token::Dot | Token::LeftBracket)));
+            if is_signed_decimal && !has_postfix {
+                // A bare `-<int>` is a single literal, so it can reach the type's minimum
+                let kind = self.parse_int_literal(slice, true)?;
+                return Ok(Expr::new(kind, self.span_from(start)));
+            }
+
+            // Postfix operators bind tighter, so they apply to the literal before the `-`
+            let literal = Expr::new(self.parse_int_literal(slice, false)?, self.prev_span);
+            self.parse_postfix_ops(literal)?
+        } else {
+            self.parse_unary_expr()?
+        };
+        Ok(Expr::new(
+            ExprKind::Unary {
+                val: Box::new(val),
+                op,
+            },
+            self.span_from(start),
+        ))
+    }
+
     fn parse_args_in_decl(&mut self) -> Result<Vec<ArgDecl>, ParseError> {
-        let left_paren_tok = self.next_or_error()?;
-        if left_paren_tok != Token::LeftParen {
-            return Err(ParseError::ExpectedDifferentToken {
-                expected: Token::LeftParen,
-                found: left_paren_tok,
-            });
-        }
+        self.expect_token(Token::LeftParen)?;
 
         let mut args = Vec::new();
         loop {
@@ -569,36 +2149,41 @@ impl<'source> Parser<'source> {
                     return Ok(args);
                 }
                 Token::Ident => {
-                    let name_slice = self.slice();
+                    let start = self.peek_span().start;
                     self.pop(); // pop the name token
+                    let name_slice = self.slice();
+                    let name_span = self.prev_span;
 
-                    let type_annot = self.parse_type_annot()?.ok_or(ParseError::MissingTypeAnnot)?;
+                    let type_annot = self
+                        .parse_type_annot()?
+                        .ok_or(ParseErrorKind::MissingTypeAnnot.at(name_span))?;
+                    let span = self.span_from(start);
 
                     let comma_tok = self.peek_or_error()?;
                     if comma_tok == Token::Comma {
                         self.pop();
                     } else if comma_tok != Token::RightParen {
-                        return Err(ParseError::ExpectedDifferentTokens {
+                        return Err(ParseErrorKind::ExpectedDifferentTokens {
                             expected: vec![Token::Comma, Token::RightParen],
                             found: comma_tok,
-                        });
+                        }
+                        .at(self.peek_span()));
                     }
 
-                    args.push(ArgDecl { attributes: vec![], name: name_slice.to_string(), type_name: type_annot });
+                    args.push(ArgDecl {
+                        attributes: vec![],
+                        name: name_slice.to_string(),
+                        type_annot,
+                        span,
+                    });
                 }
-                t => return Err(ParseError::UnexpectedToken(t))
+                t => return Err(ParseErrorKind::UnexpectedToken(t).at(self.peek_span())),
             };
         }
     }
 
     fn parse_args_in_call(&mut self) -> Result<Vec<Expr>, ParseError> {
-        let left_paren_tok = self.next_or_error()?;
-        if left_paren_tok != Token::LeftParen {
-            return Err(ParseError::ExpectedDifferentToken {
-                expected: Token::LeftParen,
-                found: left_paren_tok,
-            });
-        }
+        self.expect_token(Token::LeftParen)?;
 
         let mut args = Vec::new();
         loop {
@@ -609,50 +2194,354 @@ impl<'source> Parser<'source> {
                     return Ok(args);
                 }
                 _ => {
-                    args.push(self.parse_expr()?);
+                    args.push(self.parse_inner_expr()?);
 
                     let comma_tok = self.peek_or_error()?;
                     if comma_tok == Token::Comma {
                         self.pop();
                     } else if comma_tok != Token::RightParen {
-                        return Err(ParseError::ExpectedDifferentTokens {
+                        return Err(ParseErrorKind::ExpectedDifferentTokens {
                             expected: vec![Token::Comma, Token::RightParen],
                             found: comma_tok,
-                        });
+                        }
+                        .at(self.peek_span()));
+                    }
+                }
+            };
+        }
+    }
+
+    // <v>[(<args>)|.<n>|[<v>]]...
+    fn parse_postfix_expr(&mut self) -> Result<Expr, ParseError> {
+        let expr = self.parse_primary_expr()?;
+        self.parse_postfix_ops(expr)
+    }
+
+    /// Applies the calls, member accesses and indexes following `expr`.
+    fn parse_postfix_ops(&mut self, mut expr: Expr) -> Result<Expr, ParseError> {
+        let start = expr.span.start;
+
+        loop {
+            let kind = match self.peek() {
+                Some(Ok(Token::LeftParen)) => {
+                    let args = self.parse_args_in_call()?;
+                    ExprKind::Call {
+                        callee: Box::new(expr),
+                        args,
+                    }
+                }
+                Some(Ok(Token::Dot)) => {
+                    self.pop();
+                    let name = self.expect_ident()?.to_string();
+                    ExprKind::Member {
+                        target: Box::new(expr),
+                        name,
+                    }
+                }
+                Some(Ok(Token::LeftBracket)) => {
+                    self.pop();
+                    let index = self.parse_inner_expr()?;
+                    self.expect_token(Token::RightBracket)?;
+                    ExprKind::Index {
+                        target: Box::new(expr),
+                        index: Box::new(index),
                     }
                 }
+                _ => break,
             };
+            expr = Expr::new(kind, self.span_from(start));
+        }
+
+        Ok(expr)
+    }
+
+    // if<cond>(<codeBlock>|<v>)else(<codeBlock>|<v>), the `if` already consumed
+    fn parse_if_expr(&mut self, start: usize) -> Result<IfExpr, ParseError> {
+        let cond = self.parse_inner_expr()?;
+        self.parse_if_expr_arms(cond, start)
+    }
+
+    fn parse_if_expr_arms(&mut self, cond: Expr, start: usize) -> Result<IfExpr, ParseError> {
+        let arm_then = self.parse_if_expr_arm()?;
+
+        if self.peek() != Some(Ok(Token::Else)) {
+            return Err(ParseErrorKind::IfExprWithoutElse.at(Span::new(start, start + "if".len())));
+        }
+        self.pop();
+        let arm_else = self.parse_if_expr_arm()?;
+
+        Ok(IfExpr {
+            cond: Box::new(cond),
+            arm_then: Box::new(arm_then),
+            arm_else: Box::new(arm_else),
+            span: self.span_from(start),
+        })
+    }
+
+    fn parse_if_expr_arm(&mut self) -> Result<Expr, ParseError> {
+        match self.parse_block()? {
+            Some(block) => block.into_block_expr(),
+            None => self.parse_inner_expr(),
+        }
+    }
+
+    // [<v>,...] or [<k>:<v>,...]
+    /// Parses an array `[a, b]` or a dict `[k: v]` after its `[`. Whether it's a dict is decided
+    /// by the `:` after the first element, so an empty dict is written `[:]`.
+    fn parse_collection_literal(&mut self) -> Result<ExprKind, ParseError> {
+        let opened = self.prev_span;
+
+        if self.peek() == Some(Ok(Token::Colon)) {
+            self.pop();
+            self.expect_closing(Token::RightBracket, opened)?;
+            return Ok(ExprKind::Dict(Vec::new()));
+        }
+
+        let mut elements = Vec::new();
+        let mut entries = Vec::new();
+        loop {
+            match self.peek() {
+                None => {
+                    return Err(ParseErrorKind::MissingTerminatingToken {
+                        expected: Token::RightBracket,
+                        opened,
+                    }
+                    .at(self.peek_span()));
+                }
+                Some(Ok(Token::RightBracket)) => {
+                    self.pop();
+                    break;
+                }
+                Some(_) => {}
+            }
+
+            let element = self.parse_inner_expr()?;
+            let is_dict = !entries.is_empty() || (elements.is_empty() && self.peek() == Some(Ok(Token::Colon)));
+            if is_dict {
+                self.expect_token(Token::Colon)?;
+                entries.push((element, self.parse_inner_expr()?));
+            } else {
+                elements.push(element);
+            }
+
+            // On EOF the next iteration reports the missing `]`
+            match self.peek() {
+                Some(Ok(Token::Comma)) => {
+                    self.pop();
+                }
+                Some(Ok(Token::RightBracket)) | None => {}
+                Some(_) => {
+                    return Err(ParseErrorKind::ExpectedDifferentTokens {
+                        expected: vec![Token::Comma, Token::RightBracket],
+                        found: self.peek_or_error()?,
+                    }
+                    .at(self.peek_span()));
+                }
+            }
+        }
+
+        if entries.is_empty() {
+            Ok(ExprKind::Array(elements))
+        } else {
+            Ok(ExprKind::Dict(entries))
+        }
+    }
+
+    // fun(<args>)[(:|->)<T>](<codeBlock>|=><v>)
+    fn parse_lambda_expr(&mut self, start: usize) -> Result<ExprKind, ParseError> {
+        let args = self.parse_args_in_decl()?;
+        let ret_type = self.parse_ret_type_annot()?;
+        let code = self.parse_fun_body()?;
+
+        Ok(ExprKind::Lambda(Box::new(FunDeclStatement {
+            attributes: Vec::new(),
+            visibility: VisibilityAnnot::Default,
+            name: None,
+            type_params: Vec::new(),
+            ret_type,
+            args,
+            code,
+            span: self.span_from(start),
+        })))
+    }
+
+    // new<T>{[<n>:<v>,...]}, the `new` already consumed
+    fn parse_new_expr(&mut self) -> Result<ExprKind, ParseError> {
+        let type_expr = self.parse_type_expr()?;
+
+        self.expect_token(Token::LeftBrace)?;
+        let opened = self.prev_span;
+
+        let mut fields = Vec::new();
+        loop {
+            match self.peek() {
+                None => {
+                    return Err(ParseErrorKind::MissingTerminatingToken {
+                        expected: Token::RightBrace,
+                        opened,
+                    }
+                    .at(self.peek_span()));
+                }
+                Some(Ok(Token::RightBrace)) => {
+                    self.pop();
+                    break;
+                }
+                Some(_) => {}
+            }
+
+            let start = self.peek_span().start;
+            let name = self.expect_ident()?.to_string();
+            self.expect_token(Token::Colon)?;
+            let value = self.parse_inner_expr()?;
+            fields.push(FieldInit {
+                name,
+                value,
+                span: self.span_from(start),
+            });
+
+            let comma_tok = self.peek_or_error()?;
+            if comma_tok == Token::Comma {
+                self.pop();
+            } else if comma_tok != Token::RightBrace {
+                return Err(ParseErrorKind::ExpectedDifferentTokens {
+                    expected: vec![Token::Comma, Token::RightBrace],
+                    found: comma_tok,
+                }
+                .at(self.peek_span()));
+            }
+        }
+
+        Ok(ExprKind::New {
+            type_expr,
+            fields,
+        })
+    }
+
+    // (<path>|<funType>|<collectionType>)[?]
+    pub fn parse_type_expr(&mut self) -> Result<TypeExpr, ParseError> {
+        let start = self.peek_span().start;
+
+        let kind = match self.peek_or_error()? {
+            Token::LeftParen => self.parse_fun_type_expr()?,
+            Token::LeftBracket => self.parse_collection_type_expr()?,
+            _ => self.parse_path_type_expr()?,
+        };
+        let type_expr = TypeExpr {
+            kind,
+            span: self.span_from(start),
+        };
+
+        if self.peek() != Some(Ok(Token::Question)) {
+            return Ok(type_expr);
+        }
+        self.pop();
+
+        Ok(TypeExpr {
+            kind: TypeExprKind::Optional(Box::new(type_expr)),
+            span: self.span_from(start),
+        })
+    }
+
+    // <n>[.<n>...][<<T>,...>]
+    fn parse_path_type_expr(&mut self) -> Result<TypeExprKind, ParseError> {
+        let segments = self.parse_group_path()?;
+
+        let mut generics = Vec::new();
+        if self.peek() == Some(Ok(Token::LessThan)) {
+            self.pop();
+            loop {
+                generics.push(self.parse_type_expr()?);
+                if self.peek() != Some(Ok(Token::Comma)) {
+                    break;
+                }
+                self.pop();
+            }
+            self.expect_closing_angle()?;
         }
+
+        Ok(TypeExprKind::Path { segments, generics })
     }
 
-    fn parse_ident(&mut self, slice: &str) -> Result<Expr, ParseError> {
-        if let Some(next_tok_res) = self.peek() {
-            let next_tok = (*next_tok_res)?;
-            if next_tok == Token::LeftParen {
-                let args = self.parse_args_in_call()?;
-                return Ok(Expr::Call {
-                    callee: slice.to_string(),
-                    args,
-                });
+    // (<T>,...) -> <T>
+    fn parse_fun_type_expr(&mut self) -> Result<TypeExprKind, ParseError> {
+        self.expect_token(Token::LeftParen)?;
+        let opened = self.prev_span;
+
+        let mut args = Vec::new();
+        while self.peek() != Some(Ok(Token::RightParen)) {
+            args.push(self.parse_type_expr()?);
+            if self.peek() != Some(Ok(Token::Comma)) {
+                break;
             }
+            self.pop();
         }
+        self.expect_closing(Token::RightParen, opened)?;
+
+        // A parenthesised type, e.g. `((Int) -> Int)?`
+        if args.len() == 1 && self.peek() != Some(Ok(Token::Arrow)) {
+            return Ok(args.remove(0).kind);
+        }
+
+        self.expect_token(Token::Arrow)?;
+        let ret = self.parse_type_expr()?;
 
-        Ok(Expr::Read(slice.to_string()))
+        Ok(TypeExprKind::Function {
+            args,
+            ret: Box::new(ret),
+        })
+    }
+
+    // [<T>] or [<K>: <V>]
+    fn parse_collection_type_expr(&mut self) -> Result<TypeExprKind, ParseError> {
+        self.expect_token(Token::LeftBracket)?;
+        let opened = self.prev_span;
+
+        let element = self.parse_type_expr()?;
+        let kind = if self.peek() == Some(Ok(Token::Colon)) {
+            self.pop();
+            TypeExprKind::Dict {
+                key: Box::new(element),
+                value: Box::new(self.parse_type_expr()?),
+            }
+        } else {
+            TypeExprKind::Array(Box::new(element))
+        };
+        self.expect_closing(Token::RightBracket, opened)?;
+
+        Ok(kind)
     }
 
     fn parse_primary_expr(&mut self) -> Result<Expr, ParseError> {
-        let token = self.next().ok_or(ParseError::ExpectedToken)??;
+        let token = self.next_or_error()?;
         let slice = self.slice();
+        let start = self.prev_span.start;
+
+        let kind = match token {
+            Token::IntLiteral | Token::UIntLiteral => self.parse_int_literal(slice, false)?,
+            Token::FloatLiteral => self.parse_float_literal(slice)?,
+            Token::StrLiteral => self.parse_str_literal(slice)?,
+            Token::RawStrLiteral => self.parse_raw_str_literal(slice),
+            Token::CharLiteral => self.parse_char_literal(slice)?,
+            Token::True => ExprKind::Literal(LiteralExpr::Bool(true)),
+            Token::False => ExprKind::Literal(LiteralExpr::Bool(false)),
+
+            Token::Ident => ExprKind::Read(slice.to_string()),
+
+            Token::If => ExprKind::If(self.parse_if_expr(start)?),
+            Token::New => self.parse_new_expr()?,
+            Token::Fun => self.parse_lambda_expr(start)?,
+            Token::LeftBracket => self.parse_collection_literal()?,
+
+            Token::LeftParen => {
+                let opened = self.prev_span;
+                let inner = self.parse_inner_expr()?;
+                self.expect_closing(Token::RightParen, opened)?;
+                return Ok(Expr::new(inner.kind, self.span_from(start)));
+            }
 
-        match token {
-            Token::IntLiteral => self.parse_int_literal(slice),
-            Token::UIntLiteral => self.parse_uint_literal(slice),
-            Token::FloatLiteral => self.parse_float_literal(slice),
-            Token::StrLiteral => self.parse_str_literal(slice),
+            t => return Err(ParseErrorKind::UnexpectedToken(t).at(self.prev_span)),
+        };
 
-            Token::Ident => self.parse_ident(slice),
-                                                                
-            t => Err(ParseError::UnexpectedToken(t)),
-        }
+        Ok(Expr::new(kind, self.span_from(start)))
     }
 }
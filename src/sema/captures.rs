@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use crate::parser::{BlockExpr, Expr, ExprKind, FunDeclStatement, Module, RuntimeStatement};
+use crate::sema::scopes::Scopes;
+use crate::sema::visit::{self, Visitor};
+use crate::span::Span;
+
+/// How a lambda holds on to a variable of an enclosing function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureMode {
+    /// Immutable bindings are copied into the closure.
+    Copy,
+    /// `var`s are shared, so assignments on either side stay visible to the other.
+    Ref,
+}
+
+#[derive(Debug, Clone)]
+pub struct Capture {
+    pub name: String,
+    pub mode: CaptureMode,
+    /// The declaration of the captured variable.
+    pub declared: Span,
+}
+
+struct Binding {
+    mutable: bool,
+    span: Span,
+}
+
+struct LambdaFrame {
+    span: Span,
+    /// The number of scopes around the lambda. Variables resolved in one of them are captured.
+    outer_scopes: usize,
+    captures: Vec<Capture>,
+}
+
+struct CaptureAnalyzer {
+    scopes: Scopes<Binding>,
+    lambdas: Vec<LambdaFrame>,
+    captures: HashMap<Span, Vec<Capture>>,
+}
+
+impl CaptureAnalyzer {
+    /// Records a use of `name` as a capture of every lambda between the use and the declaration,
+    /// so nested lambdas capture through the lambdas around them.
+    fn resolve(&mut self, name: &str) {
+        let Some((depth, binding)) = self.scopes.resolve(name) else {
+            return; // Not a local, e.g. a function or an imported name
+        };
+
+        let mode = if binding.mutable { CaptureMode::Ref } else { CaptureMode::Copy };
+        for lambda in self.lambdas.iter_mut().rev().take_while(|lambda| lambda.outer_scopes > depth) {
+            if !lambda.captures.iter().any(|capture| capture.name == name) {
+                lambda.captures.push(Capture {
+                    name: name.to_string(),
+                    mode,
+                    declared: binding.span,
+                });
+            }
+        }
+    }
+}
+
+impl Visitor for CaptureAnalyzer {
+    fn visit_fun_decl(&mut self, decl: &FunDeclStatement) {
+        let args = decl
+            .args
+            .iter()
+            .map(|arg| {
+                let binding = Binding {
+                    mutable: false,
+                    span: arg.span,
+                };
+                (arg.name.clone(), binding)
+            })
+            .collect();
+
+        self.scopes.enter(args);
+        visit::walk_fun_decl(self, decl);
+        self.scopes.exit();
+    }
+
+    fn visit_code_block(&mut self, code: &[RuntimeStatement]) {
+        self.scopes.enter(HashMap::new());
+        visit::walk_code_block(self, code);
+        self.scopes.exit();
+    }
+
+    fn visit_block_expr(&mut self, block: &BlockExpr) {
+        self.scopes.enter(HashMap::new());
+        visit::walk_block_expr(self, block);
+        self.scopes.exit();
+    }
+
+    fn visit_statement(&mut self, statement: &RuntimeStatement) {
+        match statement {
+            RuntimeStatement::Let(decl) => {
+                visit::walk_statement(self, statement);
+                let binding = Binding {
+                    mutable: false,
+                    span: decl.span,
+                };
+                self.scopes.declare(&decl.name, binding);
+            }
+            RuntimeStatement::Var(decl) => {
+                visit::walk_statement(self, statement);
+                let binding = Binding {
+                    mutable: true,
+                    span: decl.span,
+                };
+                self.scopes.declare(&decl.name, binding);
+            }
+            RuntimeStatement::For(for_statement) => {
+                self.visit_expr(&for_statement.iterable);
+                let iterator = Binding {
+                    mutable: false,
+                    span: for_statement.span,
+                };
+                self.scopes.enter(HashMap::from([(for_statement.iterator.clone(), iterator)]));
+                visit::walk_code_block(self, &for_statement.code);
+                self.scopes.exit();
+            }
+            _ => visit::walk_statement(self, statement),
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        match &expr.kind {
+            ExprKind::Read(name) => self.resolve(name),
+            ExprKind::Lambda(decl) => {
+                self.lambdas.push(LambdaFrame {
+                    span: expr.span,
+                    outer_scopes: self.scopes.depth(),
+                    captures: Vec::new(),
+                });
+                self.visit_fun_decl(decl);
+
+                if let Some(lambda) = self.lambdas.pop() {
+                    self.captures.insert(lambda.span, lambda.captures);
+                }
+            }
+            _ => visit::walk_expr(self, expr),
+        }
+    }
+}
+
+/// Computes the variables every lambda captures from its enclosing functions, keyed by the
+/// lambda's span. Captures are listed in order of their first use.
+pub fn analyze_captures(modules: &[Module]) -> HashMap<Span, Vec<Capture>> {
+    let mut analyzer = CaptureAnalyzer {
+        scopes: Scopes::default(),
+        lambdas: Vec::new(),
+        captures: HashMap::new(),
+    };
+    for module in modules {
+        analyzer.visit_module(module);
+    }
+
+    analyzer.captures
+}
@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+
+use crate::parser::{
+    BinOp, BlockExpr, Expr, ExprKind, FunDeclStatement, LiteralExpr, Module, RuntimeStatement, TypeExpr, TypeExprKind,
+    UnaryOp,
+};
+use crate::sema::scopes::Scopes;
+use crate::sema::visit::{self, Visitor};
+use crate::sema::{SemaError, SemaErrorKind};
+
+/// The type of an expression, as far as it's known without resolving declarations.
+#[derive(Debug, Clone, PartialEq, strum_macros::Display)]
+enum Type {
+    Bool,
+    Int,
+    UInt,
+    Float,
+    Char,
+    Str,
+    Array,
+    Dict,
+    /// A type named by an annotation or a `new` expression.
+    #[strum(to_string = "{0}")]
+    Named(String),
+}
+
+impl Type {
+    fn from_annot(annot: &TypeExpr) -> Self {
+        match &annot.kind {
+            TypeExprKind::Path { segments, generics } if segments.len() == 1 && generics.is_empty() => {
+                match segments[0].as_str() {
+                    "Bool" => Self::Bool,
+                    "Int" => Self::Int,
+                    "UInt" => Self::UInt,
+                    "Float" => Self::Float,
+                    "Char" => Self::Char,
+                    "Str" => Self::Str,
+                    _ => Self::Named(annot.to_string()),
+                }
+            }
+            TypeExprKind::Array(_) => Self::Array,
+            TypeExprKind::Dict { .. } => Self::Dict,
+            _ => Self::Named(annot.to_string()),
+        }
+    }
+
+    fn is_numeric(&self) -> bool {
+        matches!(self, Self::Int | Self::UInt | Self::Float)
+    }
+}
+
+struct ConditionChecker {
+    /// The type of each local, `None` if it can't be told yet.
+    scopes: Scopes<Option<Type>>,
+    errors: Vec<SemaError>,
+}
+
+impl ConditionChecker {
+    fn declare_local(&mut self, name: &str, type_annot: Option<&TypeExpr>, value: Option<&Expr>) {
+        let ty = match type_annot {
+            Some(annot) => Some(Type::from_annot(annot)),
+            None => value.and_then(|value| self.type_of(value)),
+        };
+        self.scopes.declare(name, ty);
+    }
+
+    /// Infers the type of `expr` from its literals and the declared locals. Operators on named
+    /// types may be overloaded and blocks may shadow locals, so those are left unknown.
+    fn type_of(&self, expr: &Expr) -> Option<Type> {
+        match &expr.kind {
+            ExprKind::Literal(literal) => Some(match literal {
+                LiteralExpr::Int(..) => Type::Int,
+                LiteralExpr::UInt(..) => Type::UInt,
+                LiteralExpr::Float(..) => Type::Float,
+                LiteralExpr::Str(_) => Type::Str,
+                LiteralExpr::Char(_) => Type::Char,
+                LiteralExpr::Bool(_) => Type::Bool,
+            }),
+            ExprKind::Interpolated(_) => Some(Type::Str),
+            ExprKind::Array(_) => Some(Type::Array),
+            ExprKind::Dict(_) => Some(Type::Dict),
+            ExprKind::Read(name) => self.scopes.lookup(name).cloned().flatten(),
+            ExprKind::New { type_expr, .. } => Some(Type::from_annot(type_expr)),
+            ExprKind::Unary { val, op } => match op {
+                UnaryOp::Not => Some(Type::Bool),
+                _ => self.type_of(val).filter(Type::is_numeric),
+            },
+            ExprKind::Binary { left, right, op } => {
+                let left = self.type_of(left);
+                match op {
+                    BinOp::And | BinOp::Or => Some(Type::Bool),
+                    BinOp::Equals
+                    | BinOp::NotEquals
+                    | BinOp::Greater
+                    | BinOp::Lower
+                    | BinOp::GreaterEqual
+                    | BinOp::LowerEqual => left.filter(|ty| !matches!(ty, Type::Named(_))).map(|_| Type::Bool),
+                    _ => {
+                        let right = self.type_of(right);
+                        let is_arithmetic = |ty: &Type| ty.is_numeric() || (*op == BinOp::Add && *ty == Type::Str);
+                        left.filter(|ty| right.as_ref() == Some(ty) && is_arithmetic(ty))
+                    }
+                }
+            }
+            ExprKind::If(if_expr) => {
+                let ty = self.type_of(&if_expr.arm_then)?;
+                (self.type_of(&if_expr.arm_else).as_ref() == Some(&ty)).then_some(ty)
+            }
+            _ => None,
+        }
+    }
+
+    fn expect_bool(&mut self, expr: &Expr) {
+        if let Some(ty) = self.type_of(expr)
+            && ty != Type::Bool
+        {
+            self.errors.push(SemaErrorKind::ExpectedBool { found: ty.to_string() }.at(expr.span));
+        }
+    }
+}
+
+impl Visitor for ConditionChecker {
+    fn visit_code_block(&mut self, code: &[RuntimeStatement]) {
+        self.scopes.enter(HashMap::new());
+        visit::walk_code_block(self, code);
+        self.scopes.exit();
+    }
+
+    fn visit_block_expr(&mut self, block: &BlockExpr) {
+        self.scopes.enter(HashMap::new());
+        visit::walk_block_expr(self, block);
+        self.scopes.exit();
+    }
+
+    fn visit_fun_decl(&mut self, decl: &FunDeclStatement) {
+        let args = decl
+            .args
+            .iter()
+            .map(|arg| (arg.name.clone(), Some(Type::from_annot(&arg.type_annot))))
+            .collect();
+
+        self.scopes.enter(args);
+        visit::walk_fun_decl(self, decl);
+        self.scopes.exit();
+    }
+
+    fn visit_statement(&mut self, statement: &RuntimeStatement) {
+        match statement {
+            RuntimeStatement::Let(decl) => {
+                visit::walk_statement(self, statement);
+                self.declare_local(&decl.name, decl.type_annot.as_ref(), decl.initial_assignment.as_ref());
+            }
+            RuntimeStatement::Var(decl) => {
+                visit::walk_statement(self, statement);
+                self.declare_local(&decl.name, decl.type_annot.as_ref(), Some(&decl.initial_assignment));
+            }
+            RuntimeStatement::If(if_statement) => {
+                for arm in &if_statement.arms {
+                    self.expect_bool(&arm.cond);
+                }
+                visit::walk_statement(self, statement);
+            }
+            RuntimeStatement::While(while_statement) => {
+                self.expect_bool(&while_statement.cond);
+                visit::walk_statement(self, statement);
+            }
+            RuntimeStatement::For(statement) => {
+                self.visit_expr(&statement.iterable);
+                self.scopes.enter(HashMap::from([(statement.iterator.clone(), None)]));
+                visit::walk_code_block(self, &statement.code);
+                self.scopes.exit();
+            }
+            _ => visit::walk_statement(self, statement),
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        match &expr.kind {
+            ExprKind::If(if_expr) => self.expect_bool(&if_expr.cond),
+            ExprKind::Unary { val, op: UnaryOp::Not } => self.expect_bool(val),
+            ExprKind::Binary {
+                left,
+                right,
+                op: BinOp::And | BinOp::Or,
+            } => {
+                self.expect_bool(left);
+                self.expect_bool(right);
+            }
+            _ => {}
+        }
+
+        visit::walk_expr(self, expr);
+    }
+}
+
+/// Checks that the conditions of `if` and `while` and the operands of `!`, `&&` and `||` are
+/// `Bool`s wherever their type is known.
+pub fn check_conditions(modules: &[Module]) -> Vec<SemaError> {
+    let mut checker = ConditionChecker {
+        scopes: Scopes::default(),
+        errors: Vec::new(),
+    };
+    for module in modules {
+        checker.visit_module(module);
+    }
+
+    checker.errors
+}
@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+
+use crate::parser::{
+    ClassDeclStatement, ClassMemberStatement, Expr, ExprKind, FieldInit, GroupMemberStatement, Module, TypeExprKind,
+    VisibilityAnnot,
+};
+use crate::sema::visit::{self, Visitor};
+use crate::sema::{SemaError, SemaErrorKind};
+use crate::span::Span;
+
+struct FieldInfo<'ast> {
+    name: &'ast str,
+    is_public: bool,
+    has_default: bool,
+    span: Span,
+}
+
+/// The group a module belongs to. Modules without a `group` declaration are groups of their own.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum GroupId<'ast> {
+    Named(&'ast [String]),
+    Anonymous(usize),
+}
+
+impl<'ast> GroupId<'ast> {
+    fn of(idx: usize, module: &'ast Module) -> Self {
+        match &module.group {
+            Some(group) => GroupId::Named(&group.path),
+            None => GroupId::Anonymous(idx),
+        }
+    }
+}
+
+struct ClassInfo<'ast> {
+    /// The group of the declaring module.
+    group: GroupId<'ast>,
+    /// The names of the parent classes and interfaces.
+    parents: Vec<&'ast str>,
+    fields: Vec<FieldInfo<'ast>>,
+}
+
+impl<'ast> ClassInfo<'ast> {
+    fn new(group: GroupId<'ast>, decl: &'ast ClassDeclStatement) -> Self {
+        let fields = decl
+            .decls
+            .iter()
+            .filter_map(|member| match member {
+                ClassMemberStatement::Let(field) => Some(FieldInfo {
+                    name: &field.name,
+                    is_public: matches!(field.visibility, VisibilityAnnot::Public),
+                    has_default: field.initial_assignment.is_some(),
+                    span: field.span,
+                }),
+                ClassMemberStatement::Var(field) => Some(FieldInfo {
+                    name: &field.name,
+                    is_public: matches!(field.visibility, VisibilityAnnot::Public),
+                    has_default: true,
+                    span: field.span,
+                }),
+                ClassMemberStatement::Fun(_) => None,
+            })
+            .collect();
+
+        let parents = decl
+            .parents
+            .iter()
+            .filter_map(|parent| match &parent.kind {
+                TypeExprKind::Path { segments, .. } => segments.last().map(String::as_str),
+                _ => None,
+            })
+            .collect();
+
+        Self { group, parents, fields }
+    }
+}
+
+struct FieldInitChecker<'ast> {
+    classes: HashMap<&'ast str, Vec<ClassInfo<'ast>>>,
+    group: GroupId<'ast>,
+    errors: Vec<SemaError>,
+}
+
+impl<'ast> FieldInitChecker<'ast> {
+    /// Finds the class `name` refers to from within `group`, preferring one declared in it.
+    fn lookup(&self, name: &str, group: GroupId) -> Option<&ClassInfo<'ast>> {
+        let candidates = self.classes.get(name)?;
+        candidates
+            .iter()
+            .find(|class| class.group == group)
+            .or_else(|| candidates.first())
+    }
+
+    /// Collects the fields of `class` and the classes it inherits from, along with the group of
+    /// their declaring class. Fields of a class shadow those of its parents.
+    fn fields_of<'a>(&'a self, class: &'a ClassInfo<'ast>) -> Vec<(GroupId<'ast>, &'a FieldInfo<'ast>)> {
+        let mut fields: Vec<(GroupId, &FieldInfo)> = Vec::new();
+        let mut visited: Vec<&ClassInfo> = Vec::new();
+        let mut pending = vec![class];
+        while let Some(class) = pending.pop() {
+            if visited.iter().any(|seen| std::ptr::eq(*seen, class)) {
+                continue; // Inheritance cycle or diamond
+            }
+            visited.push(class);
+
+            for field in &class.fields {
+                if !fields.iter().any(|(_, known)| known.name == field.name) {
+                    fields.push((class.group, field));
+                }
+            }
+            // Parents that aren't checked classes, e.g. interfaces, add no fields
+            pending.extend(class.parents.iter().rev().filter_map(|parent| self.lookup(parent, class.group)));
+        }
+
+        fields
+    }
+
+    fn check_new(&mut self, class_name: &str, fields: &[FieldInit], span: Span) {
+        let Some(class) = self.lookup(class_name, self.group) else {
+            return; // Declared outside of the checked modules
+        };
+        let class_fields = self.fields_of(class);
+
+        let mut errors = Vec::new();
+        let mut initialised: HashMap<&str, Span> = HashMap::new();
+        for init in fields {
+            if let Some(first) = initialised.get(init.name.as_str()) {
+                errors.push(
+                    SemaErrorKind::DuplicateFieldInit {
+                        field: init.name.clone(),
+                        first: *first,
+                    }
+                    .at(init.span),
+                );
+                continue;
+            }
+            initialised.insert(&init.name, init.span);
+
+            match class_fields.iter().find(|(_, field)| field.name == init.name) {
+                None => errors.push(
+                    SemaErrorKind::UnknownField {
+                        class: class_name.to_string(),
+                        field: init.name.clone(),
+                    }
+                    .at(init.span),
+                ),
+                Some((group, field)) if !field.is_public && *group != self.group => errors.push(
+                    SemaErrorKind::PrivateFieldInit {
+                        class: class_name.to_string(),
+                        field: init.name.clone(),
+                        declared: field.span,
+                    }
+                    .at(init.span),
+                ),
+                Some(_) => {}
+            }
+        }
+
+        let missing: Vec<String> = class_fields
+            .iter()
+            .filter(|(_, field)| !field.has_default && !initialised.contains_key(field.name))
+            .map(|(_, field)| field.name.to_string())
+            .collect();
+        if !missing.is_empty() {
+            errors.push(
+                SemaErrorKind::MissingFieldInit {
+                    class: class_name.to_string(),
+                    fields: missing,
+                }
+                .at(span),
+            );
+        }
+
+        self.errors.extend(errors);
+    }
+}
+
+impl Visitor for FieldInitChecker<'_> {
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let ExprKind::New { type_expr, fields } = &expr.kind
+            && let TypeExprKind::Path { segments, .. } = &type_expr.kind
+            && let Some(class_name) = segments.last()
+        {
+            self.check_new(class_name, fields, expr.span);
+        }
+
+        visit::walk_expr(self, expr);
+    }
+}
+
+/// Checks that every `new` expression initialises each field of its class and the classes it
+/// inherits from exactly once, unless the field has a default value, and only sets private
+/// fields from within the defining group.
+pub fn check_field_inits(modules: &[Module]) -> Vec<SemaError> {
+    let mut classes: HashMap<&str, Vec<ClassInfo>> = HashMap::new();
+    for (idx, module) in modules.iter().enumerate() {
+        for decl in &module.decls {
+            if let GroupMemberStatement::Class(class) = decl
+                && let Some(name) = &class.name
+            {
+                classes.entry(name).or_default().push(ClassInfo::new(GroupId::of(idx, module), class));
+            }
+        }
+    }
+
+    let mut checker = FieldInitChecker {
+        classes,
+        group: GroupId::Anonymous(0),
+        errors: Vec::new(),
+    };
+    for (idx, module) in modules.iter().enumerate() {
+        checker.group = GroupId::of(idx, module);
+        checker.visit_module(module);
+    }
+
+    checker.errors
+}
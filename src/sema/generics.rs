@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use crate::parser::{
+    ClassDeclStatement, FunDeclStatement, GroupMemberStatement, InterfaceDeclStatement, Module, TypeExpr,
+    TypeExprKind, TypeParam,
+};
+use crate::sema::visit::{self, Visitor};
+use crate::sema::{SemaError, SemaErrorKind};
+use crate::span::Span;
+
+/// The number of type parameters a class or interface takes, and where it's declared.
+struct Arity {
+    params: usize,
+    declared: Span,
+}
+
+struct GenericChecker {
+    arities: HashMap<String, Arity>,
+    /// The type parameters in scope, innermost last.
+    type_params: Vec<(String, Span)>,
+    errors: Vec<SemaError>,
+}
+
+impl GenericChecker {
+    /// Brings `params` into scope for `walk`, reporting any declared twice.
+    fn with_type_params(&mut self, params: &[TypeParam], walk: impl FnOnce(&mut Self)) {
+        let scope_start = self.type_params.len();
+        for param in params {
+            if let Some((_, first)) = self.type_params[scope_start..].iter().find(|(name, _)| *name == param.name) {
+                self.errors.push(
+                    SemaErrorKind::DuplicateTypeParam {
+                        name: param.name.clone(),
+                        first: *first,
+                    }
+                    .at(param.span),
+                );
+                continue;
+            }
+            self.type_params.push((param.name.clone(), param.span));
+        }
+
+        walk(self);
+        self.type_params.truncate(scope_start);
+    }
+
+    fn check_generic_args(&mut self, type_expr: &TypeExpr) {
+        let TypeExprKind::Path { segments, generics } = &type_expr.kind else {
+            return;
+        };
+        let Some(name) = segments.last() else {
+            return;
+        };
+
+        // Type parameters shadow classes and take no generic arguments themselves
+        let type_param = (segments.len() == 1)
+            .then(|| self.type_params.iter().rev().find(|(param, _)| param == name))
+            .flatten();
+        let (expected, declared) = match (type_param, self.arities.get(name)) {
+            (Some((_, span)), _) => (0, *span),
+            (None, Some(arity)) => (arity.params, arity.declared),
+            (None, None) => return, // Declared outside of the checked modules
+        };
+
+        // The generic arguments may be left out entirely to be inferred
+        if !generics.is_empty() && generics.len() != expected {
+            self.errors.push(
+                SemaErrorKind::GenericArgCount {
+                    name: name.clone(),
+                    expected,
+                    found: generics.len(),
+                    declared,
+                }
+                .at(type_expr.span),
+            );
+        }
+    }
+}
+
+impl Visitor for GenericChecker {
+    fn visit_class_decl(&mut self, decl: &ClassDeclStatement) {
+        self.with_type_params(&decl.type_params, |checker| visit::walk_class_decl(checker, decl));
+    }
+
+    fn visit_interface_decl(&mut self, decl: &InterfaceDeclStatement) {
+        self.with_type_params(&decl.type_params, |checker| {
+            visit::walk_type_params(checker, &decl.type_params);
+            for parent in &decl.parents {
+                checker.visit_type_expr(parent);
+            }
+            for method in &decl.methods {
+                checker.with_type_params(&method.type_params, |checker| visit::walk_fun_signature(checker, method));
+            }
+        });
+    }
+
+    fn visit_fun_decl(&mut self, decl: &FunDeclStatement) {
+        self.with_type_params(&decl.type_params, |checker| visit::walk_fun_decl(checker, decl));
+    }
+
+    fn visit_type_expr(&mut self, type_expr: &TypeExpr) {
+        self.check_generic_args(type_expr);
+        visit::walk_type_expr(self, type_expr);
+    }
+}
+
+/// Checks that type parameter lists don't declare a name twice and that every class, interface
+/// and type parameter is given as many generic arguments as it takes, if any.
+pub fn check_generics(modules: &[Module]) -> Vec<SemaError> {
+    let mut arities = HashMap::new();
+    for decl in modules.iter().flat_map(|module| &module.decls) {
+        let (name, params, declared) = match decl {
+            GroupMemberStatement::Class(ClassDeclStatement {
+                name: Some(name),
+                type_params,
+                span,
+                ..
+            }) => (name, type_params, *span),
+            GroupMemberStatement::Interface(decl) => (&decl.name, &decl.type_params, decl.span),
+            _ => continue,
+        };
+        arities.entry(name.clone()).or_insert(Arity {
+            params: params.len(),
+            declared,
+        });
+    }
+
+    let mut checker = GenericChecker {
+        arities,
+        type_params: Vec::new(),
+        errors: Vec::new(),
+    };
+    for module in modules {
+        checker.visit_module(module);
+    }
+
+    checker.errors
+}
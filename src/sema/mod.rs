@@ -0,0 +1,59 @@
+pub mod captures;
+pub mod conditions;
+pub mod fields;
+pub mod generics;
+pub mod scopes;
+pub mod visit;
+
+use crate::parser::Module;
+use crate::span::Span;
+
+#[derive(thiserror::Error, Debug)]
+#[error("{kind}")]
+pub struct SemaError {
+    pub kind: SemaErrorKind,
+    pub span: Span,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum SemaErrorKind {
+    #[error("Field {field} is initialised more than once")]
+    DuplicateFieldInit { field: String, first: Span },
+
+    #[error("Class {class} has no field {field}")]
+    UnknownField { class: String, field: String },
+
+    #[error("Missing initialisers for fields of {class}: {}", fields.join(", "))]
+    MissingFieldInit { class: String, fields: Vec<String> },
+
+    #[error("Field {field} of {class} is private to its group")]
+    PrivateFieldInit { class: String, field: String, declared: Span },
+
+    #[error("Expected a Bool, found {found}")]
+    ExpectedBool { found: String },
+
+    #[error("Type parameter {name} is declared more than once")]
+    DuplicateTypeParam { name: String, first: Span },
+
+    #[error("{name} takes {expected} generic arguments, found {found}")]
+    GenericArgCount {
+        name: String,
+        expected: usize,
+        found: usize,
+        declared: Span,
+    },
+}
+
+impl SemaErrorKind {
+    pub fn at(self, span: Span) -> SemaError {
+        SemaError { kind: self, span }
+    }
+}
+
+/// Runs every semantic check over `modules`, which together form the program.
+pub fn check(modules: &[Module]) -> Vec<SemaError> {
+    let mut errors = fields::check_field_inits(modules);
+    errors.extend(conditions::check_conditions(modules));
+    errors.extend(generics::check_generics(modules));
+    errors
+}
@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+/// The locals declared in each enclosing scope of a function, innermost last, with whatever a
+/// pass tracks about each of them.
+pub struct Scopes<T> {
+    scopes: Vec<HashMap<String, T>>,
+}
+
+impl<T> Default for Scopes<T> {
+    fn default() -> Self {
+        Self { scopes: Vec::new() }
+    }
+}
+
+impl<T> Scopes<T> {
+    /// Opens a scope, e.g. for a block or with the arguments of a function.
+    pub fn enter(&mut self, scope: HashMap<String, T>) {
+        self.scopes.push(scope);
+    }
+
+    pub fn exit(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// The number of open scopes.
+    pub fn depth(&self) -> usize {
+        self.scopes.len()
+    }
+
+    /// Declares `name` in the innermost scope, shadowing any outer local of the same name.
+    pub fn declare(&mut self, name: &str, value: T) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), value);
+        }
+    }
+
+    /// Finds the innermost local called `name`, along with the index of its scope.
+    pub fn resolve(&self, name: &str) -> Option<(usize, &T)> {
+        self.scopes
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(depth, scope)| Some((depth, scope.get(name)?)))
+    }
+
+    pub fn lookup(&self, name: &str) -> Option<&T> {
+        self.resolve(name).map(|(_, value)| value)
+    }
+}
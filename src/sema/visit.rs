@@ -0,0 +1,270 @@
+use crate::parser::{
+    BlockExpr, ClassDeclStatement, ClassMemberStatement, Expr, ExprKind, FunDeclStatement, FunSignature,
+    GroupMemberStatement, InterfaceDeclStatement, InterpolatedPart, Module, RuntimeStatement, TypeExpr, TypeExprKind,
+    TypeParam,
+};
+
+/// Walks the AST. Every `visit_*` method defaults to the matching `walk_*` function, so
+/// implementors only override the nodes they care about and call `walk_*` to recurse.
+pub trait Visitor {
+    fn visit_module(&mut self, module: &Module) {
+        walk_module(self, module);
+    }
+
+    fn visit_group_member(&mut self, decl: &GroupMemberStatement) {
+        walk_group_member(self, decl);
+    }
+
+    fn visit_class_decl(&mut self, decl: &ClassDeclStatement) {
+        walk_class_decl(self, decl);
+    }
+
+    fn visit_interface_decl(&mut self, decl: &InterfaceDeclStatement) {
+        walk_interface_decl(self, decl);
+    }
+
+    fn visit_fun_decl(&mut self, decl: &FunDeclStatement) {
+        walk_fun_decl(self, decl);
+    }
+
+    fn visit_code_block(&mut self, code: &[RuntimeStatement]) {
+        walk_code_block(self, code);
+    }
+
+    fn visit_statement(&mut self, statement: &RuntimeStatement) {
+        walk_statement(self, statement);
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        walk_expr(self, expr);
+    }
+
+    fn visit_block_expr(&mut self, block: &BlockExpr) {
+        walk_block_expr(self, block);
+    }
+
+    fn visit_type_expr(&mut self, type_expr: &TypeExpr) {
+        walk_type_expr(self, type_expr);
+    }
+}
+
+pub fn walk_module<V: Visitor + ?Sized>(visitor: &mut V, module: &Module) {
+    for decl in &module.decls {
+        visitor.visit_group_member(decl);
+    }
+}
+
+pub fn walk_group_member<V: Visitor + ?Sized>(visitor: &mut V, decl: &GroupMemberStatement) {
+    match decl {
+        GroupMemberStatement::Class(decl) => visitor.visit_class_decl(decl),
+        GroupMemberStatement::Interface(decl) => visitor.visit_interface_decl(decl),
+        GroupMemberStatement::Fun(decl) => visitor.visit_fun_decl(decl),
+        GroupMemberStatement::Let(decl) => {
+            walk_type_annot(visitor, decl.type_annot.as_ref());
+            if let Some(value) = &decl.initial_assignment {
+                visitor.visit_expr(value);
+            }
+        }
+    }
+}
+
+pub fn walk_class_decl<V: Visitor + ?Sized>(visitor: &mut V, decl: &ClassDeclStatement) {
+    walk_type_params(visitor, &decl.type_params);
+    for parent in &decl.parents {
+        visitor.visit_type_expr(parent);
+    }
+
+    for member in &decl.decls {
+        match member {
+            ClassMemberStatement::Fun(decl) => visitor.visit_fun_decl(decl),
+            ClassMemberStatement::Let(decl) => {
+                walk_type_annot(visitor, decl.type_annot.as_ref());
+                if let Some(value) = &decl.initial_assignment {
+                    visitor.visit_expr(value);
+                }
+            }
+            ClassMemberStatement::Var(decl) => {
+                walk_type_annot(visitor, decl.type_annot.as_ref());
+                visitor.visit_expr(&decl.initial_assignment);
+            }
+        }
+    }
+}
+
+pub fn walk_interface_decl<V: Visitor + ?Sized>(visitor: &mut V, decl: &InterfaceDeclStatement) {
+    walk_type_params(visitor, &decl.type_params);
+    for parent in &decl.parents {
+        visitor.visit_type_expr(parent);
+    }
+
+    for method in &decl.methods {
+        walk_fun_signature(visitor, method);
+    }
+}
+
+/// Walks the type parameters and the argument and return types of a function signature.
+pub fn walk_fun_signature<V: Visitor + ?Sized>(visitor: &mut V, signature: &FunSignature) {
+    walk_type_params(visitor, &signature.type_params);
+    for arg in &signature.args {
+        visitor.visit_type_expr(&arg.type_annot);
+    }
+    walk_type_annot(visitor, signature.ret_type.as_ref());
+}
+
+pub fn walk_fun_decl<V: Visitor + ?Sized>(visitor: &mut V, decl: &FunDeclStatement) {
+    walk_type_params(visitor, &decl.type_params);
+    for arg in &decl.args {
+        visitor.visit_type_expr(&arg.type_annot);
+    }
+    walk_type_annot(visitor, decl.ret_type.as_ref());
+
+    visitor.visit_code_block(&decl.code);
+}
+
+pub fn walk_type_params<V: Visitor + ?Sized>(visitor: &mut V, params: &[TypeParam]) {
+    for param in params {
+        for bound in &param.bounds {
+            visitor.visit_type_expr(bound);
+        }
+    }
+}
+
+fn walk_type_annot<V: Visitor + ?Sized>(visitor: &mut V, type_annot: Option<&TypeExpr>) {
+    if let Some(type_annot) = type_annot {
+        visitor.visit_type_expr(type_annot);
+    }
+}
+
+pub fn walk_code_block<V: Visitor + ?Sized>(visitor: &mut V, code: &[RuntimeStatement]) {
+    for statement in code {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &RuntimeStatement) {
+    match statement {
+        RuntimeStatement::Let(decl) => {
+            walk_type_annot(visitor, decl.type_annot.as_ref());
+            if let Some(value) = &decl.initial_assignment {
+                visitor.visit_expr(value);
+            }
+        }
+        RuntimeStatement::Var(decl) => {
+            walk_type_annot(visitor, decl.type_annot.as_ref());
+            visitor.visit_expr(&decl.initial_assignment);
+        }
+        RuntimeStatement::Discard(expr) => visitor.visit_expr(expr),
+        RuntimeStatement::Return { value, .. } => {
+            if let Some(value) = value {
+                visitor.visit_expr(value);
+            }
+        }
+        RuntimeStatement::If(statement) => {
+            for arm in &statement.arms {
+                visitor.visit_expr(&arm.cond);
+                visitor.visit_code_block(&arm.code);
+            }
+            if let Some(code) = &statement.else_code {
+                visitor.visit_code_block(code);
+            }
+        }
+        RuntimeStatement::While(statement) => {
+            visitor.visit_expr(&statement.cond);
+            visitor.visit_code_block(&statement.code);
+        }
+        RuntimeStatement::For(statement) => {
+            visitor.visit_expr(&statement.iterable);
+            visitor.visit_code_block(&statement.code);
+        }
+        RuntimeStatement::Break(_) | RuntimeStatement::Continue(_) => {}
+    }
+}
+
+pub fn walk_expr<V: Visitor + ?Sized>(visitor: &mut V, expr: &Expr) {
+    match &expr.kind {
+        ExprKind::Read(_) | ExprKind::Literal(_) => {}
+        ExprKind::Call { callee, args } => {
+            visitor.visit_expr(callee);
+            for arg in args {
+                visitor.visit_expr(arg);
+            }
+        }
+        ExprKind::Member { target, .. } => visitor.visit_expr(target),
+        ExprKind::Index { target, index } => {
+            visitor.visit_expr(target);
+            visitor.visit_expr(index);
+        }
+        ExprKind::Unary { val, .. } => visitor.visit_expr(val),
+        ExprKind::Binary { left, right, .. } => {
+            visitor.visit_expr(left);
+            visitor.visit_expr(right);
+        }
+        ExprKind::Assign { target, value, .. } => {
+            visitor.visit_expr(target);
+            visitor.visit_expr(value);
+        }
+        ExprKind::Interpolated(parts) => {
+            for part in parts {
+                if let InterpolatedPart::Expr(expr) = part {
+                    visitor.visit_expr(expr);
+                }
+            }
+        }
+        ExprKind::Array(elements) => {
+            for element in elements {
+                visitor.visit_expr(element);
+            }
+        }
+        ExprKind::Dict(entries) => {
+            for (key, value) in entries {
+                visitor.visit_expr(key);
+                visitor.visit_expr(value);
+            }
+        }
+        ExprKind::If(if_expr) => {
+            visitor.visit_expr(&if_expr.cond);
+            visitor.visit_expr(&if_expr.arm_then);
+            visitor.visit_expr(&if_expr.arm_else);
+        }
+        ExprKind::Lambda(decl) => visitor.visit_fun_decl(decl),
+        ExprKind::Block(block) => visitor.visit_block_expr(block),
+        ExprKind::New { type_expr, fields } => {
+            visitor.visit_type_expr(type_expr);
+            for field in fields {
+                visitor.visit_expr(&field.value);
+            }
+        }
+    }
+}
+
+/// Walks the code of a block expression and then its value. The value is evaluated within the
+/// scope of the code, so scope-aware passes wrap this in a scope instead of visiting the code
+/// as a code block.
+pub fn walk_block_expr<V: Visitor + ?Sized>(visitor: &mut V, block: &BlockExpr) {
+    walk_code_block(visitor, &block.code);
+    if let Some(value) = &block.value {
+        visitor.visit_expr(value);
+    }
+}
+
+pub fn walk_type_expr<V: Visitor + ?Sized>(visitor: &mut V, type_expr: &TypeExpr) {
+    match &type_expr.kind {
+        TypeExprKind::Path { generics, .. } => {
+            for generic in generics {
+                visitor.visit_type_expr(generic);
+            }
+        }
+        TypeExprKind::Function { args, ret } => {
+            for arg in args {
+                visitor.visit_type_expr(arg);
+            }
+            visitor.visit_type_expr(ret);
+        }
+        TypeExprKind::Array(element) => visitor.visit_type_expr(element),
+        TypeExprKind::Dict { key, value } => {
+            visitor.visit_type_expr(key);
+            visitor.visit_type_expr(value);
+        }
+        TypeExprKind::Optional(inner) => visitor.visit_type_expr(inner),
+    }
+}
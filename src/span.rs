@@ -0,0 +1,120 @@
+use std::ops::Range;
+
+/// A byte range `start..end` into the source text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// An empty span at `offset`, used for things like EOF.
+    pub fn point(offset: usize) -> Self {
+        Self::new(offset, offset)
+    }
+
+    /// The smallest span covering both `self` and `other`.
+    pub fn to(self, other: Span) -> Self {
+        Self::new(self.start.min(other.start), self.end.max(other.end))
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+impl From<Range<usize>> for Span {
+    fn from(range: Range<usize>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+impl From<Span> for Range<usize> {
+    fn from(span: Span) -> Self {
+        span.start..span.end
+    }
+}
+
+/// A 1-based line and column, the column counted in chars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineCol {
+    pub line: usize,
+    pub col: usize,
+}
+
+/// Resolves byte offsets of a single source file into lines and columns.
+#[derive(Debug)]
+pub struct SourceMap {
+    name: String,
+    source: String,
+    line_starts: Vec<usize>,
+}
+
+impl SourceMap {
+    pub fn new(name: impl Into<String>, source: impl Into<String>) -> Self {
+        let source = source.into();
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+
+        Self {
+            name: name.into(),
+            source,
+            line_starts,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Resolves `offset` into a line and column. Offsets past the end are clamped.
+    pub fn location(&self, offset: usize) -> LineCol {
+        let offset = offset.min(self.source.len());
+        let line_idx = match self.line_starts.binary_search(&offset) {
+            Ok(idx) => idx,
+            Err(idx) => idx - 1,
+        };
+        let line_start = self.line_starts[line_idx];
+        let col = self.source[line_start..offset].chars().count() + 1;
+
+        LineCol { line: line_idx + 1, col }
+    }
+
+    /// The text of the 1-based line `line`, without its line terminator.
+    pub fn line(&self, line: usize) -> Option<&str> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let end = self
+            .line_starts
+            .get(line)
+            .map(|next| next - 1)
+            .unwrap_or(self.source.len());
+
+        Some(self.source[start..end].trim_end_matches('\r'))
+    }
+
+    /// The byte offset at which the 1-based line `line` starts.
+    pub fn line_start(&self, line: usize) -> Option<usize> {
+        self.line_starts.get(line.checked_sub(1)?).copied()
+    }
+
+    pub fn slice(&self, span: Span) -> &str {
+        &self.source[span.start..span.end]
+    }
+}
@@ -0,0 +1,51 @@
+use crate::parser::{ClassMemberStatement, ExprKind, FunDeclStatement, GroupMemberStatement, Parser, RuntimeStatement};
+use crate::span::Span;
+use super::parse_module;
+
+fn assert_implicit_return(fun: &FunDeclStatement) {
+    assert_eq!(fun.code.len(), 1);
+    match &fun.code[0] {
+        RuntimeStatement::Return { value: Some(expr), .. } => {
+            assert!(matches!(expr.kind, ExprKind::Call { .. }))
+        }
+        _ => panic!("Expected implicit return"),
+    }
+}
+
+#[test]
+fn test_arrow_body_free_function() {
+    let module = parse_module("fun duk(): Duk\n  => duk();\nfun bee() => bee() fun c() {}");
+    assert_eq!(module.decls.len(), 3);
+    match &module.decls[0] {
+        GroupMemberStatement::Fun(fun) => {
+            assert_implicit_return(fun);
+            assert_eq!(fun.span, Span::new(0, 25));
+            assert_eq!(fun.code[0].span(), Span::new(17, 25));
+        }
+        _ => panic!("Expected function declaration"),
+    }
+    assert!(matches!(&module.decls[1], GroupMemberStatement::Fun(fun) if fun.code.len() == 1));
+}
+
+#[test]
+fn test_arrow_body_method() {
+    let module = parse_module("class Duk { fun speak() => writeln(\"quak\"); @drop fun() => close(_fd) }");
+    match &module.decls[0] {
+        GroupMemberStatement::Class(class) => {
+            assert_eq!(class.decls.len(), 2);
+            for decl in &class.decls {
+                match decl {
+                    ClassMemberStatement::Fun(fun) => assert_implicit_return(fun),
+                    _ => panic!("Expected method"),
+                }
+            }
+        }
+        _ => panic!("Expected class declaration"),
+    }
+}
+
+#[test]
+fn test_missing_body_is_error() {
+    let (_, errors) = Parser::new("fun f(): Int;").parse_module();
+    assert_eq!(errors.len(), 1);
+}
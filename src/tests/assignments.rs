@@ -0,0 +1,69 @@
+use crate::parser::{BinOp, ExprKind, ParseErrorKind, Parser};
+use crate::span::Span;
+
+#[test]
+fn test_plain_assignment() {
+    match Parser::new("_ = notDiscardable()").parse_expr().unwrap().kind {
+        ExprKind::Assign { target, op, value } => {
+            assert!(matches!(target.kind, ExprKind::Read(ref s) if s == "_"));
+            assert!(op.is_none());
+            assert!(matches!(value.kind, ExprKind::Call { .. }));
+        }
+        _ => panic!("Expected assignment"),
+    }
+}
+
+#[test]
+fn test_compound_assignments() {
+    for (source, expected) in [
+        ("x += 1", BinOp::Add),
+        ("x -= 1", BinOp::Sub),
+        ("x *= 1", BinOp::Mul),
+        ("x /= 1", BinOp::Div),
+        ("x %= 1", BinOp::Mod),
+        ("x &= 1", BinOp::BitAnd),
+        ("x |= 1", BinOp::BitOr),
+        ("x ^= 1", BinOp::BitXor),
+        ("x <<= 1", BinOp::ShiftLeft),
+        ("x >>= 1", BinOp::ShiftRight),
+    ] {
+        match Parser::new(source).parse_expr().unwrap().kind {
+            ExprKind::Assign { op, .. } => assert_eq!(op, Some(expected), "{source}"),
+            _ => panic!("{source} should be an assignment"),
+        }
+    }
+}
+
+#[test]
+fn test_assignment_is_right_associative() {
+    match Parser::new("a = b += c").parse_expr().unwrap().kind {
+        ExprKind::Assign { target, value, .. } => {
+            assert!(matches!(target.kind, ExprKind::Read(ref s) if s == "a"));
+            assert!(matches!(value.kind, ExprKind::Assign { op: Some(BinOp::Add), .. }));
+        }
+        _ => panic!("Expected assignment"),
+    }
+}
+
+#[test]
+fn test_assign_to_field_and_index() {
+    assert!(matches!(
+        Parser::new("duck.age += 1").parse_expr().unwrap().kind,
+        ExprKind::Assign { ref target, .. } if matches!(target.kind, ExprKind::Member { .. })
+    ));
+    assert!(matches!(
+        Parser::new("counts[i] = 0").parse_expr().unwrap().kind,
+        ExprKind::Assign { ref target, .. } if matches!(target.kind, ExprKind::Index { .. })
+    ));
+}
+
+#[test]
+fn test_assign_to_non_lvalue_is_error() {
+    for source in ["f() = 1", "1 += 2", "a + b = c", "-a = b"] {
+        let err = Parser::new(source).parse_expr().unwrap_err();
+        assert!(matches!(err.kind, ParseErrorKind::NotAssignable), "{source}");
+    }
+
+    let err = Parser::new("f() -= 1").parse_expr().unwrap_err();
+    assert_eq!(err.span, Span::new(0, 3));
+}
@@ -0,0 +1,63 @@
+use crate::parser::{BinOp, ExprKind, Parser, TypeExprKind, UnaryOp};
+use super::extract_binary;
+
+#[test]
+fn test_bitwise_ops() {
+    for (source, expected) in [
+        ("a & b", BinOp::BitAnd),
+        ("a | b", BinOp::BitOr),
+        ("a ^ b", BinOp::BitXor),
+        ("a << b", BinOp::ShiftLeft),
+        ("a >> b", BinOp::ShiftRight),
+    ] {
+        let (op, _, _) = extract_binary(Parser::new(source).parse_expr().unwrap());
+        assert_eq!(op, expected, "{source}");
+    }
+}
+
+#[test]
+fn test_bit_not() {
+    match Parser::new("~flags").parse_expr().unwrap().kind {
+        ExprKind::Unary { op, .. } => assert_eq!(op, UnaryOp::BitNot),
+        _ => panic!("Expected unary expression"),
+    }
+}
+
+#[test]
+fn test_bitwise_precedence() {
+    // (a | (b ^ (c & (d << (e + 1))))) == f
+    let (op, left, _) = extract_binary(Parser::new("a | b ^ c & d << e + 1 == f").parse_expr().unwrap());
+    assert_eq!(op, BinOp::Equals);
+    let (op, _, right) = extract_binary(left);
+    assert_eq!(op, BinOp::BitOr);
+    let (op, _, right) = extract_binary(right);
+    assert_eq!(op, BinOp::BitXor);
+    let (op, _, right) = extract_binary(right);
+    assert_eq!(op, BinOp::BitAnd);
+    let (op, _, right) = extract_binary(right);
+    assert_eq!(op, BinOp::ShiftLeft);
+    assert_eq!(extract_binary(right).0, BinOp::Add);
+}
+
+#[test]
+fn test_nested_generics_close_with_shift_token() {
+    match Parser::new("new List<List<Int>> {}").parse_expr().unwrap().kind {
+        ExprKind::New { type_expr, .. } => {
+            let TypeExprKind::Path { generics, .. } = type_expr.kind else {
+                panic!("Expected a path type");
+            };
+            let TypeExprKind::Path { ref generics, .. } = generics[0].kind else {
+                panic!("Expected a path type");
+            };
+            assert_eq!(generics.len(), 1);
+        }
+        _ => panic!("Expected new expression"),
+    }
+}
+
+#[test]
+fn test_overload_attributes() {
+    assert_eq!(BinOp::BitXor.overload_attribute(), Some("bitXor"));
+    assert_eq!(BinOp::And.overload_attribute(), None);
+    assert_eq!(UnaryOp::BitNot.overload_attribute(), Some("bitNot"));
+}
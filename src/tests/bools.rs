@@ -0,0 +1,102 @@
+use crate::parser::{ExprKind, LiteralExpr, Parser};
+use crate::sema::{self, SemaError, SemaErrorKind};
+use super::parse_module;
+
+fn check(source: &str) -> Vec<SemaError> {
+    let module = parse_module(source);
+    sema::check(&[module])
+}
+
+fn expect_non_bool(errors: &[SemaError], source: &str, found: &[(&str, &str)]) {
+    let found_errors: Vec<(&str, &str)> = errors
+        .iter()
+        .map(|err| match &err.kind {
+            SemaErrorKind::ExpectedBool { found } => (&source[err.span.start..err.span.end], found.as_str()),
+            x => panic!("Expected non-Bool error, found {:?}", x),
+        })
+        .collect();
+    assert_eq!(found_errors, found);
+}
+
+#[test]
+fn test_bool_literals() {
+    assert!(matches!(
+        Parser::new("true").parse_expr().unwrap().kind,
+        ExprKind::Literal(LiteralExpr::Bool(true))
+    ));
+
+    let decl = Parser::new("let quit = false;").parse_immutable_variable_decl().unwrap();
+    assert!(matches!(
+        decl.initial_assignment.unwrap().kind,
+        ExprKind::Literal(LiteralExpr::Bool(false))
+    ));
+}
+
+#[test]
+fn test_bool_conditions_pass() {
+    let source = "fun main(done: Bool) {
+        let quit = false;
+        while !quit && !done { quit = 1 < 2; }
+        if quit == done || true {} else if (2 >= 1) {}
+        let x = if quit { 1 } else { 2 };
+    }";
+    assert!(check(source).is_empty());
+}
+
+#[test]
+fn test_non_bool_conditions() {
+    let source = r#"fun main(name: Str) {
+        let count = 3;
+        if count {}
+        while name + "!" {}
+        let x = if 1.5 { 1 } else { 2 };
+        let d = new Duck {};
+        if !d || [1] {}
+    }"#;
+    let errors = check(source);
+    expect_non_bool(
+        &errors,
+        source,
+        &[
+            ("count", "Int"),
+            (r#"name + "!""#, "Str"),
+            ("1.5", "Float"),
+            ("[1]", "Array"),
+            ("d", "Duck"),
+        ],
+    );
+}
+
+#[test]
+fn test_unknown_types_are_not_reported() {
+    let source = "fun main() {
+        let num = Random.randInt(0, 10);
+        if num {}
+        for i in items { while i {} }
+        if a + b {}
+    }";
+    assert!(check(source).is_empty());
+}
+
+#[test]
+fn test_locals_are_scoped() {
+    let source = "fun main() {
+        let flag = true;
+        if flag { let flag = 1; }
+        while flag {}
+        let y = if flag { let other = 1; other } else { 0 };
+        if other {}
+    }";
+    assert!(check(source).is_empty());
+}
+
+#[test]
+fn test_block_values_see_block_locals() {
+    let source = "fun main(n: Int) {
+        let x = if true { let n = true; !n } else { false };
+        let y = if true { let n = 1; !n } else { false };
+    }";
+    let errors = check(source);
+    expect_non_bool(&errors, source, &[("n", "Int")]);
+    assert_eq!(errors[0].span.start, source.rfind("!n").unwrap() + 1);
+}
@@ -0,0 +1,47 @@
+use crate::parser::{ExprKind, LiteralExpr, ParseErrorKind, Parser};
+use crate::span::Span;
+
+fn parse_char(source: &str) -> char {
+    match Parser::new(source).parse_expr().unwrap().kind {
+        ExprKind::Literal(LiteralExpr::Char(c)) => c,
+        x => panic!("Expected char literal, found {:?}", x),
+    }
+}
+
+#[test]
+fn test_char_literal() {
+    assert_eq!(parse_char("'a'"), 'a');
+    assert_eq!(parse_char("'ü'"), 'ü');
+}
+
+#[test]
+fn test_char_escapes() {
+    assert_eq!(parse_char(r"'\n'"), '\n');
+    assert_eq!(parse_char(r"'\''"), '\'');
+    assert_eq!(parse_char(r"'\\'"), '\\');
+    assert_eq!(parse_char(r"'\x41'"), 'A');
+    assert_eq!(parse_char(r"'\u{1F986}'"), '🦆');
+}
+
+#[test]
+fn test_empty_char_literal() {
+    let err = Parser::new("''").parse_expr().unwrap_err();
+    assert!(matches!(err.kind, ParseErrorKind::EmptyCharLiteral));
+    assert_eq!(err.span, Span::new(0, 2));
+}
+
+#[test]
+fn test_multiple_chars_in_char_literal() {
+    let err = Parser::new("'ab'").parse_expr().unwrap_err();
+    assert!(matches!(err.kind, ParseErrorKind::MultipleCharsInCharLiteral));
+}
+
+#[test]
+fn test_invalid_char_escape() {
+    let err = Parser::new(r"'\q'").parse_expr().unwrap_err();
+    assert!(matches!(err.kind, ParseErrorKind::InvalidEscape(ref esc) if esc == r"\q"));
+    assert_eq!(err.span, Span::new(1, 3));
+
+    let err = Parser::new(r"'\u{D800}'").parse_expr().unwrap_err();
+    assert_eq!(err.span, Span::new(1, 9));
+}
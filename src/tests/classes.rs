@@ -0,0 +1,67 @@
+use crate::parser::{ClassMemberStatement, GroupMemberStatement, Parser, VisibilityAnnot};
+use super::parse_class;
+
+#[test]
+fn test_class_with_members() {
+    let class = parse_class(
+        "class Duck {
+            priv let _name: Str;
+            pub fun quack() {
+                writeln(_name);
+            }
+        }",
+    );
+    assert_eq!(class.name.as_deref(), Some("Duck"));
+    assert!(class.parents.is_empty());
+    assert_eq!(class.decls.len(), 2);
+    match &class.decls[0] {
+        ClassMemberStatement::Let(field) => {
+            assert_eq!(field.name, "_name");
+            assert!(matches!(field.visibility, VisibilityAnnot::Private));
+        }
+        _ => panic!("Expected field"),
+    }
+    match &class.decls[1] {
+        ClassMemberStatement::Fun(method) => {
+            assert_eq!(method.name.as_deref(), Some("quack"));
+            assert!(matches!(method.visibility, VisibilityAnnot::Public));
+            assert_eq!(method.code.len(), 1);
+        }
+        _ => panic!("Expected method"),
+    }
+}
+
+#[test]
+fn test_class_parents() {
+    let class = parse_class("class Duk : Animal, Named {}");
+    assert_eq!(class.parents.iter().map(ToString::to_string).collect::<Vec<_>>(), vec!["Animal", "Named"]);
+
+    let class = parse_class("class TestError -> Error { pub let text: Str; }");
+    assert_eq!(class.parents.iter().map(ToString::to_string).collect::<Vec<_>>(), vec!["Error"]);
+}
+
+#[test]
+fn test_class_attributes_and_anonymous_method() {
+    let class = parse_class("@stack pub class Handle { var _fd: Int = 0; @drop fun() {} }");
+    assert_eq!(class.attributes[0].name, "stack");
+    assert!(matches!(class.visibility, VisibilityAnnot::Public));
+    assert!(matches!(&class.decls[0], ClassMemberStatement::Var(field) if field.name == "_fd"));
+    match &class.decls[1] {
+        ClassMemberStatement::Fun(method) => {
+            assert!(method.name.is_none());
+            assert_eq!(method.attributes[0].name, "drop");
+        }
+        _ => panic!("Expected method"),
+    }
+}
+
+#[test]
+fn test_class_member_recovery() {
+    let (module, errors) = Parser::new("class A { let x: Int; 42; fun f() {} } fun g() {}").parse_module();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(module.decls.len(), 2);
+    match &module.decls[0] {
+        GroupMemberStatement::Class(class) => assert_eq!(class.decls.len(), 2),
+        _ => panic!("Expected class declaration"),
+    }
+}
@@ -0,0 +1,87 @@
+use crate::parser::{Expr, ExprKind, LiteralExpr, ParseErrorKind, Parser};
+use crate::lexer::Token;
+use crate::span::Span;
+
+fn parse(source: &str) -> Expr {
+    Parser::new(source).parse_expr().unwrap()
+}
+
+#[test]
+fn test_array_literal() {
+    match parse("[ 1, 2, 3 ]").kind {
+        ExprKind::Array(elements) => {
+            assert_eq!(elements.len(), 3);
+            assert!(matches!(elements[2].kind, ExprKind::Literal(LiteralExpr::Int(3, None))));
+        }
+        x => panic!("Expected array literal, found {:?}", x),
+    }
+}
+
+#[test]
+fn test_dict_literal() {
+    let expr = parse(r#"[ "name": "john", "born": "1977" ]"#);
+    assert_eq!(expr.span, Span::new(0, 34));
+    match expr.kind {
+        ExprKind::Dict(entries) => {
+            assert_eq!(entries.len(), 2);
+            assert!(matches!(entries[0].0.kind, ExprKind::Literal(LiteralExpr::Str(ref k)) if k == "name"));
+            assert!(matches!(entries[1].1.kind, ExprKind::Literal(LiteralExpr::Str(ref v)) if v == "1977"));
+        }
+        x => panic!("Expected dict literal, found {:?}", x),
+    }
+}
+
+#[test]
+fn test_trailing_commas() {
+    assert!(matches!(parse("[1, 2,]").kind, ExprKind::Array(ref e) if e.len() == 2));
+    assert!(matches!(parse("[a: 1,]").kind, ExprKind::Dict(ref e) if e.len() == 1));
+}
+
+#[test]
+fn test_empty_literals() {
+    assert!(matches!(parse("[]").kind, ExprKind::Array(ref e) if e.is_empty()));
+    assert!(matches!(parse("[:]").kind, ExprKind::Dict(ref e) if e.is_empty()));
+}
+
+#[test]
+fn test_indexing_literals() {
+    match parse("[1, 2][0]").kind {
+        ExprKind::Index { target, .. } => assert!(matches!(target.kind, ExprKind::Array(_))),
+        x => panic!("Expected index expression, found {:?}", x),
+    }
+    match parse(r#"["a": 1]["a"]"#).kind {
+        ExprKind::Index { target, .. } => assert!(matches!(target.kind, ExprKind::Dict(_))),
+        x => panic!("Expected index expression, found {:?}", x),
+    }
+}
+
+#[test]
+fn test_nested_collections() {
+    match parse("[[1], [k: [2]]]").kind {
+        ExprKind::Array(elements) => {
+            assert!(matches!(elements[0].kind, ExprKind::Array(_)));
+            assert!(matches!(elements[1].kind, ExprKind::Dict(_)));
+        }
+        x => panic!("Expected array literal, found {:?}", x),
+    }
+}
+
+#[test]
+fn test_mixed_entries() {
+    let err = Parser::new("[1, a: 2]").parse_expr().unwrap_err();
+    assert!(matches!(err.kind, ParseErrorKind::ExpectedDifferentTokens { found: Token::Colon, .. }));
+
+    let err = Parser::new("[a: 1, 2]").parse_expr().unwrap_err();
+    assert!(matches!(err.kind, ParseErrorKind::ExpectedDifferentToken { expected: Token::Colon, .. }));
+}
+
+#[test]
+fn test_unclosed_array() {
+    let err = Parser::new("[1, 2").parse_expr().unwrap_err();
+    match err.kind {
+        ParseErrorKind::MissingTerminatingToken { expected: Token::RightBracket, opened } => {
+            assert_eq!(opened, Span::new(0, 1))
+        }
+        x => panic!("Expected missing `]`, found {:?}", x),
+    }
+}
@@ -0,0 +1,64 @@
+use crate::parser::{ExprKind, ParseErrorKind, Parser, RuntimeStatement};
+use super::parse_body;
+
+#[test]
+fn test_if_else_if_else() {
+    let code = parse_body("if a { x(); } else if b { y(); } else if c {} else { z(); } done();");
+    assert_eq!(code.len(), 2);
+    match &code[0] {
+        RuntimeStatement::If(statement) => {
+            assert_eq!(statement.arms.len(), 3);
+            assert!(matches!(&statement.arms[1].cond.kind, ExprKind::Read(name) if name == "b"));
+            assert_eq!(statement.arms[2].code.len(), 0);
+            assert_eq!(statement.else_code.as_ref().map(Vec::len), Some(1));
+        }
+        _ => panic!("Expected if statement"),
+    }
+}
+
+#[test]
+fn test_if_without_else() {
+    let code = parse_body("if a == b { x(); }");
+    assert!(matches!(&code[0], RuntimeStatement::If(statement) if statement.arms.len() == 1 && statement.else_code.is_none()));
+}
+
+#[test]
+fn test_while_with_break_and_continue() {
+    let code = parse_body("while running { if done { break; } continue; }");
+    match &code[0] {
+        RuntimeStatement::While(statement) => {
+            assert!(matches!(&statement.code[0], RuntimeStatement::If(_)));
+            assert!(matches!(&statement.code[1], RuntimeStatement::Continue(_)));
+        }
+        _ => panic!("Expected while statement"),
+    }
+}
+
+#[test]
+fn test_for_in() {
+    let code = parse_body("for duck in ducks() { duck(); }");
+    match &code[0] {
+        RuntimeStatement::For(statement) => {
+            assert_eq!(statement.iterator, "duck");
+            assert!(matches!(statement.iterable.kind, ExprKind::Call { .. }));
+            assert_eq!(statement.code.len(), 1);
+        }
+        _ => panic!("Expected for statement"),
+    }
+}
+
+#[test]
+fn test_for_condition_is_while() {
+    let code = parse_body("for i < 10 { i = i + 1; }");
+    assert!(matches!(&code[0], RuntimeStatement::While(statement) if matches!(statement.cond.kind, ExprKind::Binary { .. })));
+}
+
+#[test]
+fn test_for_in_requires_identifier() {
+    for (source, head) in [("fun f() { for f() in xs {} }", "f()"), ("fun f() { for x.y in xs {} }", "x.y")] {
+        let (_, errors) = Parser::new(source).parse_module();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0].kind, ParseErrorKind::InvalidLoopVariable), "{:?}", errors);
+        assert_eq!(&source[errors[0].span.start..errors[0].span.end], head);
+    }
+}
@@ -0,0 +1,65 @@
+use crate::diagnostics::Diagnostic;
+use crate::parser::Parser;
+use crate::span::SourceMap;
+
+fn render_module_error(source: &str) -> String {
+    let map = SourceMap::new("test.duk", source);
+    let (_, errors) = Parser::new(map.source()).parse_module();
+    Diagnostic::from(&errors[0]).render(&map, false)
+}
+
+#[test]
+fn test_render_snippet_with_carets() {
+    let rendered = render_module_error("fun foo(a) {}");
+    assert_eq!(
+        rendered,
+        "error: A type annotation is missing\n \
+         --> test.duk:1:9\n  \
+         |\n\
+         1 | fun foo(a) {}\n  \
+         |         ^\n  \
+         |\n  \
+         = help: add a type annotation, e.g. `name: Type`\n"
+    );
+}
+
+#[test]
+fn test_render_opened_here_label() {
+    let rendered = render_module_error("fun foo() {\n");
+    assert_eq!(
+        rendered,
+        "error: Missing terminating token: `}`\n \
+         --> test.duk:2:1\n  \
+         |\n\
+         1 | fun foo() {\n  \
+         |           - opened here\n\
+         2 | \n  \
+         | ^ expected `}`\n"
+    );
+}
+
+#[test]
+fn test_render_without_color_has_no_escapes() {
+    let rendered = render_module_error("fun foo(1) {}");
+    assert!(!rendered.contains('\x1b'));
+
+    let map = SourceMap::new("test.duk", "fun foo(1) {}");
+    let (_, errors) = Parser::new(map.source()).parse_module();
+    assert!(Diagnostic::from(&errors[0]).render(&map, true).contains('\x1b'));
+}
+
+#[test]
+fn test_render_labels_on_one_line_in_column_order() {
+    let map = SourceMap::new("test.duk", "class A { let a: Int; }\nfun f() => new A { a: 1, a: 2 }");
+    let (module, _) = Parser::new(map.source()).parse_module();
+    let errors = crate::sema::check(&[module]);
+    assert_eq!(
+        Diagnostic::from(&errors[0]).render(&map, false),
+        "error: Field a is initialised more than once\n \
+         --> test.duk:2:26\n  \
+         |\n\
+         2 | fun f() => new A { a: 1, a: 2 }\n  \
+         |                    ---- first initialised here\n  \
+         |                          ^^^^\n"
+    );
+}
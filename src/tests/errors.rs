@@ -1,19 +1,19 @@
-use crate::parser::{ParseError, Parser};
+use crate::parser::{ParseErrorKind, Parser};
 
 #[test]
 fn test_error_expected_token() {
     let mut parser = Parser::new("");
-    assert!(matches!(parser.parse_expr().unwrap_err(), ParseError::ExpectedToken));
+    assert!(matches!(parser.parse_expr().unwrap_err().kind, ParseErrorKind::ExpectedToken));
 }
 
 #[test]
 fn test_error_unexpected_token() {
     let mut parser = Parser::new(";");
-    assert!(matches!(parser.parse_expr().unwrap_err(), ParseError::UnexpectedToken(_)));
+    assert!(matches!(parser.parse_expr().unwrap_err().kind, ParseErrorKind::UnexpectedToken(_)));
 }
 
 #[test]
 fn test_error_missing_closing_paren() {
     let mut parser = Parser::new("foo(1, 2");
-    assert!(matches!(parser.parse_expr().unwrap_err(), ParseError::ExpectedToken));
+    assert!(matches!(parser.parse_expr().unwrap_err().kind, ParseErrorKind::ExpectedToken));
 }
@@ -1,23 +1,23 @@
-use crate::parser::{Expr, Parser};
+use crate::parser::{ExprKind, Parser};
 
 #[test]
 fn test_function_call_no_args() {
-    match Parser::new("foo()").parse_expr().unwrap() {
-        Expr::Call {
+    match Parser::new("foo()").parse_expr().unwrap().kind {
+        ExprKind::Call {
             ref callee,
             ref args,
-        } if callee == "foo" && args.is_empty() => {}
+        } if matches!(callee.kind, ExprKind::Read(ref name) if name == "foo") && args.is_empty() => {}
         _ => panic!("Expected function call with no args"),
     }
 }
 
 #[test]
 fn test_function_call_with_args() {
-    match Parser::new("foo(1, 2,)").parse_expr().unwrap() {
-        Expr::Call {
+    match Parser::new("foo(1, 2,)").parse_expr().unwrap().kind {
+        ExprKind::Call {
             ref callee,
             ref args,
-        } if callee == "foo" => {
+        } if matches!(callee.kind, ExprKind::Read(ref name) if name == "foo") => {
             assert_eq!(args.len(), 2);
         }
         _ => panic!("Expected function call with args"),
@@ -0,0 +1,78 @@
+use crate::parser::GroupMemberStatement;
+use crate::sema::{self, SemaErrorKind};
+use super::{parse_class, parse_module};
+
+#[test]
+fn test_class_type_params() {
+    let class = parse_class("class Box<T: Animal, U>: Container<T> { let value: T; }");
+    assert_eq!(class.type_params.len(), 2);
+    assert_eq!(class.type_params[0].name, "T");
+    assert_eq!(class.type_params[0].bounds[0].to_string(), "Animal");
+    assert!(class.type_params[1].bounds.is_empty());
+    assert_eq!(class.parents[0].to_string(), "Container<T>");
+}
+
+#[test]
+fn test_multiple_bounds_and_nested_generics() {
+    let class = parse_class("class Sorted<T: Comparable<T> + Named> {}");
+    let bounds: Vec<String> = class.type_params[0].bounds.iter().map(ToString::to_string).collect();
+    assert_eq!(bounds, vec!["Comparable<T>", "Named"]);
+}
+
+#[test]
+fn test_fun_type_params() {
+    let module = parse_module("fun map<T, R>(items: [T], f: (T) -> R): [R] => items.map(f)");
+    match &module.decls[0] {
+        GroupMemberStatement::Fun(decl) => {
+            let names: Vec<&str> = decl.type_params.iter().map(|param| param.name.as_str()).collect();
+            assert_eq!(names, vec!["T", "R"]);
+        }
+        _ => panic!("Expected function declaration"),
+    }
+}
+
+#[test]
+fn test_interface_type_params() {
+    let module = parse_module("interface Iterable<T> { fun next(): T?; }");
+    match &module.decls[0] {
+        GroupMemberStatement::Interface(decl) => assert_eq!(decl.type_params[0].name, "T"),
+        _ => panic!("Expected interface declaration"),
+    }
+}
+
+#[test]
+fn test_valid_generics_pass() {
+    let source = "class Result<T, E> {}
+        interface Iterable<T> { fun next<U>(with: U): T?; }
+        fun f<T>(res: Result<T, Str>, items: Iterable<T>): Result {
+            let r = new Result<Int, Str> {};
+        }";
+    assert!(sema::check(&[parse_module(source)]).is_empty());
+}
+
+#[test]
+fn test_duplicate_type_params() {
+    let errors = sema::check(&[parse_module("fun f<T, U, T>() {} class Box<T> { fun g<T>() {} }")]);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(&errors[0].kind, SemaErrorKind::DuplicateTypeParam { name, .. } if name == "T"));
+}
+
+#[test]
+fn test_generic_arg_counts() {
+    let source = "class Box<T> {}
+        class Duck {}
+        fun f<T>(a: Box<Int, Str>, b: Duck<Int>, c: T<Int>) {
+            let d = new Box<Int> {};
+        }";
+    let errors = sema::check(&[parse_module(source)]);
+    let counts: Vec<(&str, usize, usize)> = errors
+        .iter()
+        .map(|err| match &err.kind {
+            SemaErrorKind::GenericArgCount {
+                name, expected, found, ..
+            } => (name.as_str(), *expected, *found),
+            x => panic!("Expected generic argument count error, found {:?}", x),
+        })
+        .collect();
+    assert_eq!(counts, vec![("Box", 1, 2), ("Duck", 0, 1), ("T", 0, 1)]);
+}
@@ -1,9 +1,9 @@
-use crate::parser::{Expr, Parser};
+use crate::parser::{ExprKind, Parser};
 
 #[test]
 fn test_identifier_read() {
-    match Parser::new("foo").parse_expr().unwrap() {
-        Expr::Read(ref s) if s == "foo" => {}
+    match Parser::new("foo").parse_expr().unwrap().kind {
+        ExprKind::Read(ref s) if s == "foo" => {}
         _ => panic!("Expected identifier read"),
     }
 }
@@ -0,0 +1,139 @@
+use crate::parser::{Expr, ExprKind, GroupMemberStatement, IfExpr, LiteralExpr, ParseErrorKind, Parser, RuntimeStatement};
+use crate::span::Span;
+use super::{parse_fun, parse_module};
+
+fn extract_if(expr: Expr) -> IfExpr {
+    match expr.kind {
+        ExprKind::If(if_expr) => if_expr,
+        _ => panic!("Expected if expression"),
+    }
+}
+
+#[test]
+fn test_braceless_if_expr() {
+    let if_expr = extract_if(Parser::new("if success \"yes\" else \"no\"").parse_expr().unwrap());
+    assert!(matches!(if_expr.cond.kind, ExprKind::Read(ref name) if name == "success"));
+    assert!(matches!(if_expr.arm_then.kind, ExprKind::Literal(LiteralExpr::Str(ref s)) if s == "yes"));
+    assert!(matches!(if_expr.arm_else.kind, ExprKind::Literal(LiteralExpr::Str(ref s)) if s == "no"));
+}
+
+#[test]
+fn test_block_if_expr() {
+    let if_expr = extract_if(Parser::new("if fails { log(); success(10) } else { error() }").parse_expr().unwrap());
+    match if_expr.arm_then.kind {
+        ExprKind::Block(ref block) => {
+            assert_eq!(block.code.len(), 1);
+            assert!(matches!(block.value.as_deref(), Some(Expr { kind: ExprKind::Call { .. }, .. })));
+        }
+        _ => panic!("Expected block arm"),
+    }
+    assert!(matches!(if_expr.arm_else.kind, ExprKind::Block(ref block) if block.value.is_some()));
+}
+
+#[test]
+fn test_else_if_expr() {
+    let if_expr = extract_if(Parser::new("if a 1 else if b 2 else 3").parse_expr().unwrap());
+    let nested = extract_if(*if_expr.arm_else);
+    assert!(matches!(nested.arm_else.kind, ExprKind::Literal(LiteralExpr::Int(3, None))));
+}
+
+#[test]
+fn test_if_expr_in_let_and_arrow_body() {
+    let source = "fun f(fails: Bool): Int\n  => if fails { 1 } else { 2 }\nfun g() { let message = if ok \"a\" else \"b\"; writeln(message) }";
+    match &parse_module(source).decls[1] {
+        GroupMemberStatement::Fun(fun) => {
+            assert_eq!(fun.code.len(), 2);
+            assert!(matches!(
+                &fun.code[0],
+                RuntimeStatement::Let(decl) if matches!(decl.initial_assignment, Some(Expr { kind: ExprKind::If(_), .. }))
+            ));
+        }
+        _ => panic!("Expected function declaration"),
+    }
+}
+
+fn block_value(expr: Expr) -> Expr {
+    match expr.kind {
+        ExprKind::Block(block) => *block.value.expect("Expected block value"),
+        _ => panic!("Expected block arm"),
+    }
+}
+
+#[test]
+fn test_nested_if_is_block_value() {
+    let code = parse_fun("fun f() { let x: Int = if a { if b { 1 } else { 2 } } else { 3 }; }").code;
+    let RuntimeStatement::Let(decl) = &code[0] else {
+        panic!("Expected let statement");
+    };
+    let if_expr = extract_if(decl.initial_assignment.clone().unwrap());
+    let nested = extract_if(block_value(*if_expr.arm_then));
+    assert!(matches!(block_value(*nested.arm_then).kind, ExprKind::Literal(LiteralExpr::Int(1, None))));
+    assert!(matches!(block_value(*nested.arm_else).kind, ExprKind::Literal(LiteralExpr::Int(2, None))));
+}
+
+#[test]
+fn test_nested_braceless_if() {
+    let code = parse_fun("fun f() => if a { if true 1 else 2 } else { 3 }").code;
+    let RuntimeStatement::Return { value: Some(value), .. } = &code[0] else {
+        panic!("Expected return");
+    };
+    let nested = extract_if(block_value(*extract_if(value.clone()).arm_then));
+    assert!(matches!(nested.cond.kind, ExprKind::Literal(LiteralExpr::Bool(true))));
+    assert!(matches!(nested.arm_else.kind, ExprKind::Literal(LiteralExpr::Int(2, None))));
+}
+
+#[test]
+fn test_trailing_if_else_chain_is_arm_value() {
+    let value = Parser::new("if c { log(); if a { 1 } else if b { 2 } else { 3 } } else { 0 }").parse_expr().unwrap();
+    let nested = extract_if(block_value(*extract_if(value).arm_then));
+    let nested = extract_if(*nested.arm_else);
+    assert!(matches!(nested.cond.kind, ExprKind::Read(ref name) if name == "b"));
+
+    // Without an else the block has no value
+    let value = Parser::new("if c { if a { 1 } } else { 0 }").parse_expr().unwrap();
+    match extract_if(value).arm_then.kind {
+        ExprKind::Block(block) => {
+            assert!(block.value.is_none());
+            assert!(matches!(block.code[0], RuntimeStatement::If(_)));
+        }
+        _ => panic!("Expected block arm"),
+    }
+}
+
+#[test]
+fn test_if_in_statement_position_stays_statement() {
+    for source in [
+        "fun f() { if a { 1 } else { 2 } }",
+        "fun f() { if a { if b { 1 } else { 2 } } else { 3 } }",
+        "fun f() { if a { 1 } else { 2 } done(); }",
+    ] {
+        let code = parse_fun(source).code;
+        let RuntimeStatement::If(statement) = &code[0] else {
+            panic!("Expected if statement for {source}");
+        };
+        let discards_if = |statement: &RuntimeStatement| {
+            matches!(statement, RuntimeStatement::Discard(Expr { kind: ExprKind::If(_), .. }))
+        };
+        assert!(!statement.arms[0].code.iter().any(discards_if));
+    }
+
+    // A function body's trailing value isn't returned
+    let code = parse_fun("fun f(x: Int): Int { x + 1 }").code;
+    assert!(matches!(&code[0], RuntimeStatement::Discard(_)));
+
+    let (_, errors) = Parser::new("fun f() { if a 1 else 2 }").parse_module();
+    assert!(matches!(errors[0].kind, ParseErrorKind::MissingCodeBlock), "{:?}", errors);
+}
+
+#[test]
+fn test_if_expr_needs_else() {
+    for source in ["if c a b", "if c { 1 }"] {
+        let err = Parser::new(source).parse_expr().unwrap_err();
+        assert!(matches!(err.kind, ParseErrorKind::IfExprWithoutElse), "{source}: {:?}", err);
+        assert_eq!(err.span, Span::new(0, 2));
+    }
+
+    let (_, errors) = Parser::new("fun f() { let x = if c { 1 }; }").parse_module();
+    assert!(matches!(errors[0].kind, ParseErrorKind::IfExprWithoutElse), "{:?}", errors);
+    assert_eq!(errors[0].span, Span::new(18, 20));
+}
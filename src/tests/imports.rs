@@ -0,0 +1,80 @@
+use crate::lexer::Token;
+use crate::parser::{ImportKind, ParseErrorKind, Parser};
+use crate::sema::{self, SemaErrorKind};
+use crate::span::Span;
+use super::parse_module;
+
+#[test]
+fn test_group_and_imports() {
+    let source = "import Foundation.Console.writeln;
+        import Ffi.Posix;
+        import Foundation.Net.Http as Web;
+        import Foundation.Results.*;
+
+        group Example.If;
+
+        fun main() {}";
+    let module = parse_module(source);
+
+    let group = module.group.unwrap();
+    assert_eq!(group.path, vec!["Example", "If"]);
+    assert_eq!(&source[group.span.start..group.span.end], "group Example.If;");
+    assert_eq!(module.decls.len(), 1);
+
+    let bindings: Vec<Option<&str>> = module.imports.iter().map(|import| import.binding()).collect();
+    assert_eq!(bindings, vec![Some("writeln"), Some("Posix"), Some("Web"), None]);
+    assert_eq!(module.imports[2].path, vec!["Foundation", "Net", "Http"]);
+    assert!(matches!(module.imports[3].kind, ImportKind::Glob));
+    assert_eq!(module.imports[3].path, vec!["Foundation", "Results"]);
+
+    let span = module.imports[0].span;
+    assert_eq!(&source[span.start..span.end], "import Foundation.Console.writeln;");
+}
+
+#[test]
+fn test_module_without_group() {
+    let module = parse_module("fun main() {}");
+    assert!(module.group.is_none());
+    assert!(module.imports.is_empty());
+}
+
+#[test]
+fn test_duplicate_group_decl() {
+    let (module, errors) = Parser::new("group A; group B; fun main() {}").parse_module();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0].kind, ParseErrorKind::DuplicateGroupDecl { .. }));
+    assert_eq!(module.group.unwrap().path, vec!["A"]);
+    assert_eq!(module.decls.len(), 1);
+}
+
+#[test]
+fn test_invalid_imports_recover() {
+    for source in ["import; fun main() {}", "import A.*.B; fun main() {}", "import A.* as B; fun main() {}"] {
+        let (module, errors) = Parser::new(source).parse_module();
+        assert_eq!(errors.len(), 1, "{source}: {:?}", errors);
+        assert_eq!(module.decls.len(), 1, "{source}");
+    }
+}
+
+#[test]
+fn test_import_is_a_path_not_a_call() {
+    let (module, errors) = Parser::new("import Foundation.Console.prompt()\nimport Animals.Duck;").parse_module();
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(matches!(
+        errors[0].kind,
+        ParseErrorKind::ExpectedDifferentToken { expected: Token::Semicolon, found: Token::LeftParen }
+    ));
+    assert_eq!(errors[0].span, Span::new(32, 33));
+    assert_eq!(module.imports.len(), 1);
+}
+
+#[test]
+fn test_private_fields_within_group() {
+    let (animals, _) = Parser::new("group Animals; class Duck { let _name: Str; }").parse_module();
+    let (pond, _) = Parser::new("group Animals; fun hatch() => new Duck { _name: \"pep\" }").parse_module();
+    let (main, _) = Parser::new("group Main; fun main() => new Duck { _name: \"pep\" }").parse_module();
+
+    let errors = sema::check(&[animals, pond, main]);
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(matches!(errors[0].kind, SemaErrorKind::PrivateFieldInit { ref field, .. } if field == "_name"));
+}
@@ -0,0 +1,99 @@
+use crate::parser::{ExprKind, LiteralExpr, NumSuffix, ParseErrorKind, Parser, UnaryOp};
+use crate::span::Span;
+
+fn parse_literal(source: &str) -> LiteralExpr {
+    match Parser::new(source).parse_expr().unwrap().kind {
+        ExprKind::Literal(literal) => literal,
+        x => panic!("Expected literal, found {:?}", x),
+    }
+}
+
+fn expect_out_of_range(source: &str, ty: NumSuffix) -> Span {
+    let err = Parser::new(source).parse_expr().unwrap_err();
+    match err.kind {
+        ParseErrorKind::LiteralOutOfRange(found) if found == ty => err.span,
+        x => panic!("Expected literal out of range for {ty}, found {:?}", x),
+    }
+}
+
+#[test]
+fn test_radix_prefixes() {
+    assert!(matches!(parse_literal("0xFF"), LiteralExpr::Int(255, None)));
+    assert!(matches!(parse_literal("0b1010_1010"), LiteralExpr::Int(0b1010_1010, None)));
+    assert!(matches!(parse_literal("0o755"), LiteralExpr::Int(0o755, None)));
+    assert!(matches!(parse_literal("0x_dead_BEEF"), LiteralExpr::Int(0xdead_beef, None)));
+    assert!(matches!(parse_literal("1_000_000"), LiteralExpr::Int(1_000_000, None)));
+}
+
+#[test]
+fn test_int_suffixes() {
+    assert!(matches!(parse_literal("127i8"), LiteralExpr::Int(127, Some(NumSuffix::I8))));
+    assert!(matches!(parse_literal("255u8"), LiteralExpr::UInt(255, Some(NumSuffix::U8))));
+    assert!(matches!(parse_literal("0x0100u16"), LiteralExpr::UInt(256, Some(NumSuffix::U16))));
+    assert!(matches!(parse_literal("0b1i64"), LiteralExpr::Int(1, Some(NumSuffix::I64))));
+    assert!(matches!(parse_literal("7u"), LiteralExpr::UInt(7, None)));
+}
+
+#[test]
+fn test_float_suffixes() {
+    assert!(matches!(parse_literal("1.5f32"), LiteralExpr::Float(1.5, Some(NumSuffix::F32))));
+    assert!(matches!(parse_literal("2f64"), LiteralExpr::Float(2.0, Some(NumSuffix::F64))));
+    assert!(matches!(parse_literal("0.25"), LiteralExpr::Float(0.25, None)));
+}
+
+#[test]
+fn test_bit_patterns_wrap_into_signed_types() {
+    assert!(matches!(parse_literal("0xFFi8"), LiteralExpr::Int(-1, Some(NumSuffix::I8))));
+    assert!(matches!(parse_literal("0xFFFF_FFFF_FFFF_FFFF"), LiteralExpr::Int(-1, None)));
+}
+
+#[test]
+fn test_negated_minimum() {
+    let expr = Parser::new("-128i8").parse_expr().unwrap();
+    assert!(matches!(expr.kind, ExprKind::Literal(LiteralExpr::Int(-128, Some(NumSuffix::I8)))));
+    assert_eq!(expr.span, Span::new(0, 6));
+    assert!(matches!(parse_literal("-9223372036854775808"), LiteralExpr::Int(i64::MIN, None)));
+    assert!(matches!(parse_literal("-5"), LiteralExpr::Int(-5, None)));
+
+    expect_out_of_range("128i8", NumSuffix::I8);
+    expect_out_of_range("-(128i8)", NumSuffix::I8);
+}
+
+#[test]
+fn test_negated_bit_patterns_and_unsigned_stay_unary() {
+    let negated = |source: &str| match Parser::new(source).parse_expr().unwrap().kind {
+        ExprKind::Unary { op: UnaryOp::Negative, val } => match val.kind {
+            ExprKind::Literal(literal) => literal,
+            x => panic!("Expected literal, found {:?}", x),
+        },
+        x => panic!("Expected negation, found {:?}", x),
+    };
+
+    // The `-` applies to the value of the bit pattern, so `-0xFFi8` is `-(-1)`
+    assert!(matches!(negated("-0xFFi8"), LiteralExpr::Int(-1, Some(NumSuffix::I8))));
+    assert!(matches!(negated("-0x80i8"), LiteralExpr::Int(-128, Some(NumSuffix::I8))));
+    assert!(matches!(negated("-0b101"), LiteralExpr::Int(5, None)));
+    assert!(matches!(negated("-5u8"), LiteralExpr::UInt(5, Some(NumSuffix::U8))));
+}
+
+#[test]
+fn test_negated_literal_with_postfix_ops() {
+    // `.abs()` binds tighter than `-`, so its receiver is the positive literal
+    assert_eq!(expect_out_of_range("-128i8.abs()", NumSuffix::I8), Span::new(1, 6));
+    expect_out_of_range("-9223372036854775808.abs()", NumSuffix::I64);
+
+    match Parser::new("-1i8.abs()").parse_expr().unwrap().kind {
+        ExprKind::Unary { val, .. } => assert!(matches!(val.kind, ExprKind::Call { .. })),
+        x => panic!("Expected unary expression, found {:?}", x),
+    }
+}
+
+#[test]
+fn test_out_of_range_literals() {
+    assert_eq!(expect_out_of_range("x + 256u8", NumSuffix::U8), Span::new(4, 9));
+    expect_out_of_range("0x1_0000_0000_0000_0000", NumSuffix::I64);
+    expect_out_of_range("18446744073709551616u", NumSuffix::U64);
+    expect_out_of_range("9223372036854775808", NumSuffix::I64);
+    expect_out_of_range("0x1FFu8", NumSuffix::U8);
+    expect_out_of_range("1.0e39f32", NumSuffix::F32);
+}
@@ -0,0 +1,52 @@
+use crate::parser::{ClassMemberStatement, GroupMemberStatement, InterfaceKind, Parser};
+use super::parse_module;
+
+#[test]
+fn test_interface_with_signatures() {
+    let module = parse_module("interface Animal { fun speak(); fun name(loud: Bool): Str; }");
+    match &module.decls[0] {
+        GroupMemberStatement::Interface(interface) => {
+            assert_eq!(interface.kind, InterfaceKind::Interface);
+            assert_eq!(interface.name, "Animal");
+            assert_eq!(interface.methods.len(), 2);
+            assert_eq!(interface.methods[1].name.as_deref(), Some("name"));
+            assert_eq!(interface.methods[1].args.len(), 1);
+            assert_eq!(interface.methods[1].ret_type.as_ref().unwrap().to_string(), "Str");
+        }
+        _ => panic!("Expected interface declaration"),
+    }
+}
+
+#[test]
+fn test_trait_with_attributes_and_parents() {
+    let module = parse_module("@maxStack(128) trait Limited : Animal {}");
+    match &module.decls[0] {
+        GroupMemberStatement::Interface(interface) => {
+            assert_eq!(interface.kind, InterfaceKind::Trait);
+            assert_eq!(interface.attributes[0].name, "maxStack");
+            assert_eq!(interface.parents.iter().map(ToString::to_string).collect::<Vec<_>>(), vec!["Animal"]);
+            assert!(interface.methods.is_empty());
+        }
+        _ => panic!("Expected trait declaration"),
+    }
+}
+
+#[test]
+fn test_implementing_class() {
+    let source = "interface Animal { fun speak(); } class Duk : Animal { fun speak() { writeln(\"quak\"); } }";
+    let module = parse_module(source);
+    match &module.decls[1] {
+        GroupMemberStatement::Class(class) => {
+            assert_eq!(class.parents.iter().map(ToString::to_string).collect::<Vec<_>>(), vec!["Animal"]);
+            assert!(matches!(&class.decls[0], ClassMemberStatement::Fun(method) if method.name.as_deref() == Some("speak")));
+        }
+        _ => panic!("Expected class declaration"),
+    }
+}
+
+#[test]
+fn test_interface_method_with_body_is_error() {
+    let (module, errors) = Parser::new("interface Animal { fun speak() {} }").parse_module();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(module.decls.len(), 1);
+}
@@ -0,0 +1,113 @@
+use crate::parser::{BinOp, Expr, ExprKind, InterpolatedPart, LiteralExpr, ParseErrorKind, Parser};
+use crate::span::Span;
+
+fn parse_parts(source: &str) -> Vec<InterpolatedPart> {
+    match Parser::new(source).parse_expr().unwrap().kind {
+        ExprKind::Interpolated(parts) => parts,
+        x => panic!("Expected interpolated string, found {:?}", x),
+    }
+}
+
+fn expect_str(part: &InterpolatedPart, expected: &str) {
+    match part {
+        InterpolatedPart::Str(s) if s == expected => {}
+        x => panic!("Expected text {:?}, found {:?}", expected, x),
+    }
+}
+
+fn expect_expr(part: &InterpolatedPart) -> &Expr {
+    match part {
+        InterpolatedPart::Expr(expr) => expr,
+        x => panic!("Expected an interpolated expression, found {:?}", x),
+    }
+}
+
+#[test]
+fn test_interpolated_string() {
+    let source = r#""Hi {name}, you are {age + 1}""#;
+    let parts = parse_parts(source);
+    assert_eq!(parts.len(), 4);
+
+    expect_str(&parts[0], "Hi ");
+    let name = expect_expr(&parts[1]);
+    assert!(matches!(name.kind, ExprKind::Read(ref n) if n == "name"));
+    assert_eq!(name.span, Span::new(5, 9));
+
+    expect_str(&parts[2], ", you are ");
+    let age = expect_expr(&parts[3]);
+    assert!(matches!(age.kind, ExprKind::Binary { op: BinOp::Add, .. }));
+    assert_eq!(&source[age.span.start..age.span.end], "age + 1");
+}
+
+#[test]
+fn test_plain_string_stays_literal() {
+    match Parser::new(r#""no interpolation""#).parse_expr().unwrap().kind {
+        ExprKind::Literal(LiteralExpr::Str(ref s)) if s == "no interpolation" => {}
+        x => panic!("Expected string literal, found {:?}", x),
+    }
+}
+
+#[test]
+fn test_escaped_braces() {
+    match Parser::new(r#""\{not} {x}""#).parse_expr().unwrap().kind {
+        ExprKind::Interpolated(parts) => {
+            expect_str(&parts[0], "{not} ");
+            expect_expr(&parts[1]);
+        }
+        x => panic!("Expected interpolated string, found {:?}", x),
+    }
+}
+
+#[test]
+fn test_nested_strings_and_blocks() {
+    let parts = parse_parts(r#""{names["duck}"]} and {if a { "{b}" } else { "}" }}""#);
+    assert_eq!(parts.len(), 3);
+
+    assert!(matches!(expect_expr(&parts[0]).kind, ExprKind::Index { .. }));
+    expect_str(&parts[1], " and ");
+    assert!(matches!(expect_expr(&parts[2]).kind, ExprKind::If(_)));
+}
+
+#[test]
+fn test_member_call_in_interpolation() {
+    let parts = parse_parts(r#""Hi! I'm {duck.name}!""#);
+    assert!(matches!(expect_expr(&parts[1]).kind, ExprKind::Member { .. }));
+    expect_str(&parts[2], "!");
+}
+
+#[test]
+fn test_empty_braces_are_text() {
+    match Parser::new(r#""{}".format(x)"#).parse_expr().unwrap().kind {
+        ExprKind::Call { callee, .. } => match callee.kind {
+            ExprKind::Member { ref target, .. } => {
+                assert!(matches!(target.kind, ExprKind::Literal(LiteralExpr::Str(ref s)) if s == "{}"));
+            }
+            x => panic!("Expected member access, found {:?}", x),
+        },
+        x => panic!("Expected call, found {:?}", x),
+    }
+
+    let parts = parse_parts(r#""{} is {x}""#);
+    expect_str(&parts[0], "{} is ");
+    expect_expr(&parts[1]);
+}
+
+#[test]
+fn test_empty_interpolation() {
+    let err = Parser::new(r#""a { } b""#).parse_expr().unwrap_err();
+    assert!(matches!(err.kind, ParseErrorKind::EmptyInterpolation));
+    assert_eq!(err.span, Span::new(3, 6));
+}
+
+#[test]
+fn test_trailing_tokens_in_interpolation() {
+    let err = Parser::new(r#""{a b}""#).parse_expr().unwrap_err();
+    assert!(matches!(err.kind, ParseErrorKind::UnexpectedToken(_)));
+    assert_eq!(err.span, Span::new(4, 5));
+}
+
+#[test]
+fn test_unterminated_interpolation() {
+    let err = Parser::new(r#""{a""#).parse_expr().unwrap_err();
+    assert!(matches!(err.kind, ParseErrorKind::ErrorToken));
+}
@@ -0,0 +1,143 @@
+use crate::parser::{Expr, ExprKind, FunDeclStatement, GroupMemberStatement, Parser, RuntimeStatement};
+use crate::sema::captures::{self, Capture, CaptureMode};
+use super::parse_module;
+
+fn parse_lambda(source: &str) -> (FunDeclStatement, Expr) {
+    let expr = Parser::new(source).parse_expr().unwrap();
+    match &expr.kind {
+        ExprKind::Lambda(decl) => ((**decl).clone(), expr),
+        x => panic!("Expected lambda, found {:?}", x),
+    }
+}
+
+/// The captures of each lambda in `source`, in source order.
+fn captures_of(source: &str) -> Vec<Vec<(String, CaptureMode)>> {
+    let module = parse_module(source);
+
+    let mut captures: Vec<_> = captures::analyze_captures(&[module]).into_iter().collect();
+    captures.sort_by_key(|(span, _)| span.start);
+    captures
+        .into_iter()
+        .map(|(_, captures): (_, Vec<Capture>)| {
+            captures.into_iter().map(|capture| (capture.name, capture.mode)).collect()
+        })
+        .collect()
+}
+
+fn owned(captures: &[(&str, CaptureMode)]) -> Vec<(String, CaptureMode)> {
+    captures.iter().map(|(name, mode)| (name.to_string(), *mode)).collect()
+}
+
+#[test]
+fn test_lambda_with_block_body() {
+    let (decl, expr) = parse_lambda("fun(x: Int) { ret x * x; }");
+    assert!(decl.name.is_none());
+    assert_eq!(decl.args.len(), 1);
+    assert!(matches!(decl.code[0], RuntimeStatement::Return { .. }));
+    assert_eq!(expr.span.end, 26);
+}
+
+#[test]
+fn test_lambda_with_arrow_body_and_ret_type() {
+    let (decl, _) = parse_lambda("fun(con: Connection) -> Response => HtmlResponse(\"<h1>test</h1>\")");
+    assert_eq!(decl.ret_type.as_ref().unwrap().to_string(), "Response");
+    assert!(matches!(decl.code[0], RuntimeStatement::Return { value: Some(_), .. }));
+
+    let (decl, _) = parse_lambda("fun(): Int => 1");
+    assert_eq!(decl.ret_type.as_ref().unwrap().to_string(), "Int");
+}
+
+#[test]
+fn test_lambda_as_argument() {
+    let expr = Parser::new(r#"server.get("/", fun(con: Connection) => respond(con))"#)
+        .parse_expr()
+        .unwrap();
+    match expr.kind {
+        ExprKind::Call { args, .. } => assert!(matches!(args[1].kind, ExprKind::Lambda(_))),
+        x => panic!("Expected call, found {:?}", x),
+    }
+}
+
+#[test]
+fn test_arrow_ret_type_on_fun_decl() {
+    let module = parse_module("fun f() -> Int => 1");
+    match &module.decls[0] {
+        GroupMemberStatement::Fun(decl) => assert_eq!(decl.ret_type.as_ref().unwrap().to_string(), "Int"),
+        _ => panic!("Expected function declaration"),
+    }
+}
+
+#[test]
+fn test_let_with_block_lambda_needs_no_semicolon() {
+    let source = "fun lambda() {\n  let square = fun(x: Int) { ret x * x; }\n  var inc = fun(x: Int) { ret x + 1; }\n}";
+    let module = parse_module(source);
+    match &module.decls[0] {
+        GroupMemberStatement::Fun(decl) => {
+            assert!(matches!(decl.code[0], RuntimeStatement::Let(_)));
+            assert!(matches!(decl.code[1], RuntimeStatement::Var(_)));
+        }
+        _ => panic!("Expected function declaration"),
+    }
+
+    // An arrow body doesn't end in a block
+    let (_, errors) = Parser::new("fun lambda() {\n  let square = fun(x: Int) => x * x\n  done();\n}").parse_module();
+    assert!(!errors.is_empty());
+}
+
+#[test]
+fn test_named_lambda_is_rejected() {
+    assert!(Parser::new("fun named() {}").parse_expr().is_err());
+}
+
+#[test]
+fn test_capture_modes() {
+    let source = "fun main(base: Int) {
+        let step = 2;
+        var total = 0;
+        let add = fun(x: Int) { total += x * step + base; };
+    }";
+    assert_eq!(
+        captures_of(source),
+        vec![owned(&[
+            ("total", CaptureMode::Ref),
+            ("step", CaptureMode::Copy),
+            ("base", CaptureMode::Copy),
+        ])]
+    );
+}
+
+#[test]
+fn test_locals_and_globals_are_not_captured() {
+    let source = "fun main() {
+        let f = fun(x: Int) { let y = x; for i in range(y) { print(i); } };
+    }";
+    assert_eq!(captures_of(source), vec![vec![]]);
+}
+
+#[test]
+fn test_nested_lambdas_capture_through_parents() {
+    let source = "fun main() {
+        var count = 0;
+        let outer = fun() {
+            let local = 1;
+            let inner = fun() => count + local;
+        };
+    }";
+    assert_eq!(
+        captures_of(source),
+        vec![
+            owned(&[("count", CaptureMode::Ref)]),
+            owned(&[("count", CaptureMode::Ref), ("local", CaptureMode::Copy)]),
+        ]
+    );
+}
+
+#[test]
+fn test_shadowed_and_out_of_scope_variables() {
+    let source = "fun main() {
+        let a = 1;
+        if a > 0 { var b = 2; }
+        let f = fun(a: Int) => a + b;
+    }";
+    assert_eq!(captures_of(source), vec![vec![]]);
+}
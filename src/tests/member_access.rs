@@ -0,0 +1,73 @@
+use crate::parser::{Expr, ExprKind, Parser};
+use crate::span::Span;
+
+fn extract_call(expr: Expr) -> (Expr, Vec<Expr>) {
+    match expr.kind {
+        ExprKind::Call { callee, args } => (*callee, args),
+        _ => panic!("Expected call"),
+    }
+}
+
+fn extract_member(expr: Expr) -> (Expr, String) {
+    match expr.kind {
+        ExprKind::Member { target, name } => (*target, name),
+        _ => panic!("Expected member access"),
+    }
+}
+
+#[test]
+fn test_field_access() {
+    let (target, name) = extract_member(Parser::new("duck.name").parse_expr().unwrap());
+    assert!(matches!(target.kind, ExprKind::Read(ref s) if s == "duck"));
+    assert_eq!(name, "name");
+}
+
+#[test]
+fn test_method_call() {
+    let expr = Parser::new("myDuck.quack(1)").parse_expr().unwrap();
+    assert_eq!(expr.span, Span::new(0, 15));
+    let (callee, args) = extract_call(expr);
+    assert_eq!(args.len(), 1);
+    let (target, name) = extract_member(callee);
+    assert!(matches!(target.kind, ExprKind::Read(ref s) if s == "myDuck"));
+    assert_eq!(name, "quack");
+}
+
+#[test]
+fn test_chained_calls() {
+    let (callee, args) = extract_call(Parser::new("f()(x)").parse_expr().unwrap());
+    assert_eq!(args.len(), 1);
+    let (inner_callee, inner_args) = extract_call(callee);
+    assert!(inner_args.is_empty());
+    assert!(matches!(inner_callee.kind, ExprKind::Read(ref s) if s == "f"));
+
+    let (callee, _) = extract_call(Parser::new("query(\"Guess: \").toInt()").parse_expr().unwrap());
+    let (target, name) = extract_member(callee);
+    assert_eq!(name, "toInt");
+    assert!(matches!(target.kind, ExprKind::Call { .. }));
+}
+
+#[test]
+fn test_index() {
+    match Parser::new("a[i + 1].len").parse_expr().unwrap().kind {
+        ExprKind::Member { target, .. } => match target.kind {
+            ExprKind::Index { target, index } => {
+                assert!(matches!(target.kind, ExprKind::Read(ref s) if s == "a"));
+                assert!(matches!(index.kind, ExprKind::Binary { .. }));
+            }
+            _ => panic!("Expected index"),
+        },
+        _ => panic!("Expected member access"),
+    }
+}
+
+#[test]
+fn test_postfix_binds_tighter_than_binary() {
+    match Parser::new("a.b + c.d()").parse_expr().unwrap().kind {
+        ExprKind::Binary { left, right, .. } => {
+            assert!(matches!(left.kind, ExprKind::Member { .. }));
+            assert!(matches!(right.kind, ExprKind::Call { .. }));
+        }
+        _ => panic!("Expected binary expression"),
+    }
+}
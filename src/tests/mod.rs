@@ -4,3 +4,67 @@ pub mod literals;
 pub mod expressions;
 pub mod errors;
 pub mod parse_assignment;
+pub mod spans;
+pub mod diagnostics;
+pub mod recovery;
+pub mod statements;
+pub mod classes;
+pub mod interfaces;
+pub mod arrow_bodies;
+pub mod control_flow;
+pub mod if_expressions;
+pub mod member_access;
+pub mod new_exprs;
+pub mod unary_logical;
+pub mod bitwise;
+pub mod precedence;
+pub mod assignments;
+pub mod char_literals;
+pub mod strings;
+pub mod interpolation;
+pub mod int_literals;
+pub mod collections;
+pub mod bools;
+pub mod lambdas;
+pub mod types;
+pub mod generics;
+pub mod imports;
+
+use crate::parser::{
+    BinOp, ClassDeclStatement, Expr, ExprKind, FunDeclStatement, GroupMemberStatement, Module, Parser, RuntimeStatement,
+};
+
+/// Parses a module that must not have any errors.
+pub fn parse_module(source: &str) -> Module {
+    let (module, errors) = Parser::new(source).parse_module();
+    assert!(errors.is_empty(), "Unexpected errors: {:?}", errors);
+    module
+}
+
+/// The first declaration of `source`, which must be a function.
+pub fn parse_fun(source: &str) -> FunDeclStatement {
+    match parse_module(source).decls.remove(0) {
+        GroupMemberStatement::Fun(fun) => fun,
+        _ => panic!("Expected function declaration"),
+    }
+}
+
+/// The first declaration of `source`, which must be a class.
+pub fn parse_class(source: &str) -> ClassDeclStatement {
+    match parse_module(source).decls.remove(0) {
+        GroupMemberStatement::Class(class) => class,
+        _ => panic!("Expected class declaration"),
+    }
+}
+
+/// The code of a function with `body` between its braces.
+pub fn parse_body(body: &str) -> Vec<RuntimeStatement> {
+    parse_fun(&format!("fun f() {{ {body} }}")).code
+}
+
+pub fn extract_binary(expr: Expr) -> (BinOp, Expr, Expr) {
+    match expr.kind {
+        ExprKind::Binary { op, left, right } => (op, *left, *right),
+        _ => panic!("Expected binary expression"),
+    }
+}
@@ -0,0 +1,95 @@
+use crate::parser::{ExprKind, Parser, TypeExprKind};
+use crate::sema::{self, SemaErrorKind};
+use super::parse_module;
+
+#[test]
+fn test_new_with_fields() {
+    match Parser::new("new Duck { _name: name, age: 1 + 2, }").parse_expr().unwrap().kind {
+        ExprKind::New { type_expr, fields } => {
+            let TypeExprKind::Path { segments, generics } = type_expr.kind else {
+                panic!("Expected a path type");
+            };
+            assert_eq!(segments, vec!["Duck"]);
+            assert!(generics.is_empty());
+            assert_eq!(fields.len(), 2);
+            assert_eq!(fields[0].name, "_name");
+            assert!(matches!(fields[1].value.kind, ExprKind::Binary { .. }));
+        }
+        _ => panic!("Expected new expression"),
+    }
+}
+
+#[test]
+fn test_new_qualified_generic_empty() {
+    match Parser::new("new Foundation.Box<Http.Response, Int>{}").parse_expr().unwrap().kind {
+        ExprKind::New { type_expr, fields } => {
+            let TypeExprKind::Path { segments, generics } = type_expr.kind else {
+                panic!("Expected a path type");
+            };
+            assert_eq!(segments, vec!["Foundation", "Box"]);
+            assert_eq!(generics.len(), 2);
+            assert!(fields.is_empty());
+        }
+        _ => panic!("Expected new expression"),
+    }
+}
+
+#[test]
+fn test_new_field_checks() {
+    let source = "
+        class Duck { let _name: Str; pub let age: Int; let kind: Str = \"mallard\"; }
+        fun ok() => new Duck { _name: \"pep\", age: 1 }
+        fun duplicate() => new Duck { _name: \"a\", _name: \"b\", age: 1 }
+        fun unknown() => new Duck { _name: \"a\", age: 1, wings: 2 }
+        fun missing() => new Duck { age: 1 }
+        fun external() => new Goose {}
+    ";
+    let module = parse_module(source);
+
+    let errors = sema::check(&[module]);
+    assert_eq!(errors.len(), 3, "{:?}", errors);
+    assert!(matches!(errors[0].kind, SemaErrorKind::DuplicateFieldInit { ref field, .. } if field == "_name"));
+    assert!(matches!(errors[1].kind, SemaErrorKind::UnknownField { ref field, .. } if field == "wings"));
+    assert!(matches!(errors[2].kind, SemaErrorKind::MissingFieldInit { ref fields, .. } if fields == &["_name"]));
+}
+
+#[test]
+fn test_private_field_from_other_group() {
+    let (animals, _) = Parser::new("class Duck { let _name: Str; pub let age: Int; }").parse_module();
+    let (main, _) = Parser::new("fun main() { let d = new Duck { _name: \"pep\", age: 2 }; }").parse_module();
+
+    let errors = sema::check(&[animals, main]);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0].kind, SemaErrorKind::PrivateFieldInit { ref field, .. } if field == "_name"));
+}
+
+#[test]
+fn test_inherited_fields() {
+    let source = "
+        class Base { pub let a: Int; let _b: Int = 0; }
+        interface Named { fun name(): Str; }
+        class D : Base, Named { pub let c: Int; }
+        fun ok() => new D { a: 1, _b: 2, c: 3 }
+        fun missing() => new D { c: 3 }
+        class Loop : Cycle { pub let x: Int; }
+        class Cycle : Loop { pub let y: Int; }
+        fun cycle() => new Loop { x: 1, y: 2 }
+    ";
+    let module = parse_module(source);
+
+    let errors = sema::check(&[module]);
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(matches!(errors[0].kind, SemaErrorKind::MissingFieldInit { ref fields, .. } if fields == &["a"]));
+}
+
+#[test]
+fn test_private_inherited_field_from_other_group() {
+    let (animals, _) = Parser::new("group Animals; class Animal { let _name: Str; }").parse_module();
+    let (pond, _) = Parser::new("group Pond; class Duck : Animal { let _age: Int; }
+        fun hatch() => new Duck { _name: \"pep\", _age: 0 }")
+    .parse_module();
+
+    let errors = sema::check(&[animals, pond]);
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(matches!(errors[0].kind, SemaErrorKind::PrivateFieldInit { ref field, .. } if field == "_name"));
+}
@@ -1,9 +1,9 @@
-use crate::parser::{Expr, LiteralExpr, Parser};
+use crate::parser::{Expr, ExprKind, LiteralExpr, Parser};
 
 #[test]
 fn test_string_literal() {
     match Parser::new("= 10").parse_assignment().unwrap() {
-        Some(Expr::Literal(LiteralExpr::Int(10))) => {}
+        Some(Expr { kind: ExprKind::Literal(LiteralExpr::Int(10, None)), .. }) => {}
         _ => panic!("Expected string literal"),
     }
 }
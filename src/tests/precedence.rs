@@ -0,0 +1,108 @@
+use crate::parser::{BinOp, Expr, ExprKind, LiteralExpr, ParseErrorKind, Parser};
+
+// (source, precedence level) for every binary operator, independent of `BinOp::precedence`.
+const OPERATORS: &[(&str, u8)] = &[
+    ("||", 2),
+    ("&&", 3),
+    ("==", 4),
+    ("!=", 4),
+    ("<", 4),
+    (">", 4),
+    ("<=", 4),
+    (">=", 4),
+    ("|", 5),
+    ("^", 6),
+    ("&", 7),
+    ("<<", 8),
+    (">>", 8),
+    ("+", 9),
+    ("-", 9),
+    ("*", 10),
+    ("/", 10),
+    ("%", 10),
+];
+
+fn root_op(expr: &Expr) -> &BinOp {
+    match &expr.kind {
+        ExprKind::Binary { op, .. } => op,
+        _ => panic!("Expected binary expression"),
+    }
+}
+
+fn binary_children(expr: &Expr) -> (&Expr, &Expr) {
+    match &expr.kind {
+        ExprKind::Binary { left, right, .. } => (left, right),
+        _ => panic!("Expected binary expression"),
+    }
+}
+
+#[test]
+fn test_every_operator_pair() {
+    for &(first, first_prec) in OPERATORS {
+        for &(second, second_prec) in OPERATORS {
+            let source = format!("a {first} b {second} c");
+            let result = Parser::new(&source).parse_expr();
+
+            if first_prec == second_prec && first_prec == 4 {
+                assert!(
+                    matches!(result, Err(ref err) if matches!(err.kind, ParseErrorKind::NonAssociativeOperator(_))),
+                    "{source} should not associate"
+                );
+                continue;
+            }
+
+            let expr = result.unwrap_or_else(|err| panic!("{source}: {err}"));
+            let (left, right) = binary_children(&expr);
+            let groups_left = first_prec >= second_prec;
+            if groups_left {
+                assert!(matches!(left.kind, ExprKind::Binary { .. }), "{source} should group as (a {first} b) {second} c");
+                assert!(matches!(right.kind, ExprKind::Read(_)), "{source}");
+            } else {
+                assert!(matches!(left.kind, ExprKind::Read(_)), "{source} should group as a {first} (b {second} c)");
+                assert!(matches!(right.kind, ExprKind::Binary { .. }), "{source}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_greater_equal_same_level_as_greater() {
+    let source = "a >= b > c";
+    let err = Parser::new(source).parse_expr().unwrap_err();
+    assert!(matches!(err.kind, ParseErrorKind::NonAssociativeOperator(_)));
+}
+
+#[test]
+fn test_assignment_binds_loosest() {
+    for &(op, _) in OPERATORS {
+        let source = format!("a = b {op} c");
+        match Parser::new(&source).parse_expr().unwrap().kind {
+            ExprKind::Assign { value, .. } => assert!(matches!(value.kind, ExprKind::Binary { .. }), "{source}"),
+            _ => panic!("{source} should be an assignment"),
+        }
+
+        let source = format!("a {op} b = c");
+        let err = Parser::new(&source).parse_expr().unwrap_err();
+        assert!(matches!(err.kind, ParseErrorKind::NotAssignable), "{source}");
+    }
+}
+
+#[test]
+fn test_parenthesised_expr() {
+    let expr = Parser::new("(1 + 2) * 3").parse_expr().unwrap();
+    assert_eq!(root_op(&expr), &BinOp::Mul);
+    let (left, right) = binary_children(&expr);
+    assert_eq!(root_op(left), &BinOp::Add);
+    assert_eq!(left.span.start, 0);
+    assert_eq!(left.span.end, 7);
+    assert!(matches!(right.kind, ExprKind::Literal(LiteralExpr::Int(3, None))));
+
+    let expr = Parser::new("(a < b) == (c < d)").parse_expr().unwrap();
+    assert_eq!(root_op(&expr), &BinOp::Equals);
+}
+
+#[test]
+fn test_unclosed_paren() {
+    let err = Parser::new("(1 + 2").parse_expr().unwrap_err();
+    assert!(matches!(err.kind, ParseErrorKind::MissingTerminatingToken { .. }));
+}
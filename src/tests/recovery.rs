@@ -0,0 +1,53 @@
+use crate::parser::{GroupMemberStatement, ParseErrorKind, Parser};
+use crate::span::Span;
+
+fn fun_names(source: &str) -> (Vec<Option<String>>, usize) {
+    let (module, errors) = Parser::new(source).parse_module();
+    let names = module
+        .decls
+        .into_iter()
+        .map(|decl| match decl {
+            GroupMemberStatement::Fun(fun) => fun.name,
+            _ => panic!("Expected function declaration"),
+        })
+        .collect();
+    (names, errors.len())
+}
+
+#[test]
+fn test_recover_between_declarations() {
+    let (names, error_count) = fun_names("fun a(x) { } fun b() {} ) fun c(y: Int, 1) {} fun d() {}");
+    assert_eq!(names, vec![Some("b".to_string()), Some("d".to_string())]);
+    assert_eq!(error_count, 3);
+}
+
+#[test]
+fn test_recover_inside_code_block() {
+    let source = "fun a() { ) x; ] ; } fun b() {}";
+    let (module, errors) = Parser::new(source).parse_module();
+    assert_eq!(module.decls.len(), 2);
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].span, Span::new(10, 11));
+    assert_eq!(errors[1].span, Span::new(15, 16));
+}
+
+#[test]
+fn test_recover_skips_nested_blocks() {
+    let (names, error_count) = fun_names("fun a(x) { { ; } } fun b() {}");
+    assert_eq!(names, vec![Some("b".to_string())]);
+    assert_eq!(error_count, 1);
+}
+
+#[test]
+fn test_recover_unterminated_block() {
+    let (module, errors) = Parser::new("fun a() {} fun b() {").parse_module();
+    assert_eq!(module.decls.len(), 1);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0].kind, ParseErrorKind::MissingTerminatingToken { .. }));
+}
+
+#[test]
+fn test_parse_expr_reports_errors_recovered_in_blocks() {
+    let err = Parser::new("if a { let = 1; 2 } else { 3 }").parse_expr().unwrap_err();
+    assert!(matches!(err.kind, ParseErrorKind::ExpectedDifferentToken { .. }), "{:?}", err);
+}
@@ -0,0 +1,41 @@
+use crate::parser::{ExprKind, Parser};
+use crate::span::{LineCol, SourceMap, Span};
+use super::parse_module;
+
+#[test]
+fn test_binary_expr_span() {
+    let expr = Parser::new("1 + foo(2)").parse_expr().unwrap();
+    assert_eq!(expr.span, Span::new(0, 10));
+
+    let ExprKind::Binary { left, right, .. } = expr.kind else {
+        panic!("Expected binary expression");
+    };
+    assert_eq!(left.span, Span::new(0, 1));
+    assert_eq!(right.span, Span::new(4, 10));
+}
+
+#[test]
+fn test_fun_decl_span() {
+    let module = parse_module("\nfun foo(a: Int) {}\n");
+    assert_eq!(module.decls[0].span(), Span::new(1, 19));
+}
+
+#[test]
+fn test_error_span() {
+    let err = Parser::new("1 + ;").parse_expr().unwrap_err();
+    assert_eq!(err.span, Span::new(4, 5));
+
+    let err = Parser::new("foo(1").parse_expr().unwrap_err();
+    assert_eq!(err.span, Span::point(5));
+}
+
+#[test]
+fn test_source_map_location() {
+    let map = SourceMap::new("test.duk", "fun\n  ä x\n");
+    assert_eq!(map.location(0), LineCol { line: 1, col: 1 });
+    assert_eq!(map.location(4), LineCol { line: 2, col: 1 });
+    assert_eq!(map.location(9), LineCol { line: 2, col: 5 });
+    assert_eq!(map.line(2), Some("  ä x"));
+    assert_eq!(map.line(3), Some(""));
+    assert_eq!(map.line(4), None);
+}
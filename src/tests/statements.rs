@@ -0,0 +1,51 @@
+use crate::parser::{ExprKind, GroupMemberStatement, Parser, RuntimeStatement};
+use super::{parse_fun, parse_module};
+
+#[test]
+fn test_let_and_val_statements() {
+    let fun = parse_fun("fun f() { let a: Int = 1; val b = 2; let c; }");
+    assert_eq!(fun.code.len(), 3);
+    match &fun.code[0] {
+        RuntimeStatement::Let(decl) => {
+            assert_eq!(decl.name, "a");
+            assert_eq!(decl.type_annot.as_ref().unwrap().to_string(), "Int");
+            assert!(decl.initial_assignment.is_some());
+        }
+        _ => panic!("Expected let statement"),
+    }
+    assert!(matches!(&fun.code[1], RuntimeStatement::Let(decl) if decl.name == "b"));
+    assert!(matches!(&fun.code[2], RuntimeStatement::Let(decl) if decl.initial_assignment.is_none()));
+}
+
+#[test]
+fn test_var_statement() {
+    let fun = parse_fun("fun f() { var count = 0; var total: Int = 1; }");
+    assert!(matches!(&fun.code[0], RuntimeStatement::Var(decl) if decl.name == "count" && decl.type_annot.is_none()));
+    assert!(matches!(&fun.code[1], RuntimeStatement::Var(decl) if decl.type_annot.as_ref().is_some_and(|t| t.to_string() == "Int")));
+}
+
+#[test]
+fn test_discard_statement() {
+    let fun = parse_fun("fun f() { writeln(\"hi\"); _ = g(); }");
+    assert!(matches!(&fun.code[0], RuntimeStatement::Discard(expr) if matches!(expr.kind, ExprKind::Call { .. })));
+    assert!(matches!(&fun.code[1], RuntimeStatement::Discard(expr) if matches!(expr.kind, ExprKind::Assign { .. })));
+}
+
+#[test]
+fn test_return_statement() {
+    let fun = parse_fun("fun f(x: Int): Int { ret x * x; ret; }");
+    assert!(matches!(&fun.code[0], RuntimeStatement::Return { value: Some(_), .. }));
+    assert!(matches!(&fun.code[1], RuntimeStatement::Return { value: None, .. }));
+}
+
+#[test]
+fn test_group_level_let() {
+    let module = parse_module("let answer = 42; fun f() {}");
+    assert!(matches!(&module.decls[0], GroupMemberStatement::Let(decl) if decl.name == "answer"));
+}
+
+#[test]
+fn test_var_without_assignment_is_error() {
+    let (_, errors) = Parser::new("fun f() { var x: Int; }").parse_module();
+    assert_eq!(errors.len(), 1);
+}
@@ -0,0 +1,49 @@
+use crate::parser::{ExprKind, LiteralExpr, ParseErrorKind, Parser};
+use crate::span::Span;
+
+fn parse_str(source: &str) -> String {
+    match Parser::new(source).parse_expr().unwrap().kind {
+        ExprKind::Literal(LiteralExpr::Str(s)) => s,
+        x => panic!("Expected string literal, found {:?}", x),
+    }
+}
+
+#[test]
+fn test_string_escapes() {
+    assert_eq!(parse_str(r#""a\nb\t\r\0""#), "a\nb\t\r\0");
+    assert_eq!(parse_str(r#""\\ \" \'""#), "\\ \" '");
+    assert_eq!(parse_str(r#""\x41\u{e9}\u{1F986}""#), "Aé🦆");
+}
+
+#[test]
+fn test_invalid_string_escape() {
+    let err = Parser::new(r#"x = "ok \q""#).parse_expr().unwrap_err();
+    assert!(matches!(err.kind, ParseErrorKind::InvalidEscape(ref esc) if esc == r"\q"));
+    assert_eq!(err.span, Span::new(8, 10));
+}
+
+#[test]
+fn test_malformed_hex_and_unicode_escapes() {
+    for source in [r#""\x4""#, r#""\x80""#, r#""\u{}""#, r#""\u{1234567}""#, r#""\u41""#, r#""\u{110000}""#] {
+        let err = Parser::new(source).parse_expr().unwrap_err();
+        assert!(matches!(err.kind, ParseErrorKind::InvalidEscape(_)), "{source}");
+    }
+}
+
+#[test]
+fn test_multi_line_string() {
+    assert_eq!(parse_str("\"first\nsecond\""), "first\nsecond");
+}
+
+#[test]
+fn test_raw_strings() {
+    assert_eq!(parse_str(r#"r"C:\path\n""#), r"C:\path\n");
+    assert_eq!(parse_str(r###"r#"say "hi""#"###), r#"say "hi""#);
+    assert_eq!(parse_str(r###"r##"a "# b"##"###), r##"a "# b"##);
+}
+
+#[test]
+fn test_unterminated_raw_string() {
+    let err = Parser::new(r##"r#"never closed""##).parse_expr().unwrap_err();
+    assert!(matches!(err.kind, ParseErrorKind::ErrorToken));
+}
@@ -0,0 +1,84 @@
+use crate::lexer::Token;
+use crate::parser::{GroupMemberStatement, ParseErrorKind, Parser, TypeExpr, TypeExprKind};
+use crate::span::Span;
+use super::parse_module;
+
+fn parse_type(source: &str) -> TypeExpr {
+    let mut parser = Parser::new(source);
+    let type_expr = parser.parse_type_expr().unwrap();
+    assert_eq!(type_expr.span, Span::new(0, source.len()));
+    type_expr
+}
+
+#[test]
+fn test_path_types() {
+    assert_eq!(parse_type("Foundation.Primitives.Unit").to_string(), "Foundation.Primitives.Unit");
+    assert_eq!(parse_type("Result<Int, Str>").to_string(), "Result<Int, Str>");
+    assert_eq!(parse_type("Http.Response<List<Int>>").to_string(), "Http.Response<List<Int>>");
+}
+
+#[test]
+fn test_function_types() {
+    let type_expr = parse_type("(Int, Str) -> Bool");
+    match type_expr.kind {
+        TypeExprKind::Function { args, ret } => {
+            assert_eq!(args.len(), 2);
+            assert_eq!(ret.to_string(), "Bool");
+        }
+        x => panic!("Expected function type, found {:?}", x),
+    }
+
+    assert_eq!(parse_type("() -> Unit").to_string(), "() -> Unit");
+    assert_eq!(parse_type("(Int) -> (Int) -> Int").to_string(), "(Int) -> (Int) -> Int");
+}
+
+#[test]
+fn test_collection_types() {
+    assert!(matches!(parse_type("[Int]").kind, TypeExprKind::Array(_)));
+    assert!(matches!(parse_type("[Str: [Int]]").kind, TypeExprKind::Dict { .. }));
+    assert_eq!(parse_type("[Str: (Int) -> Bool]").to_string(), "[Str: (Int) -> Bool]");
+}
+
+#[test]
+fn test_optional_types() {
+    assert!(matches!(parse_type("Int?").kind, TypeExprKind::Optional(_)));
+    assert_eq!(parse_type("[Duck?]?").to_string(), "[Duck?]?");
+
+    // The `?` binds to the return type unless the function type is parenthesised
+    match parse_type("(Int) -> Int?").kind {
+        TypeExprKind::Function { ret, .. } => assert!(matches!(ret.kind, TypeExprKind::Optional(_))),
+        x => panic!("Expected function type, found {:?}", x),
+    }
+    assert_eq!(parse_type("((Int) -> Int)?").to_string(), "((Int) -> Int)?");
+}
+
+#[test]
+fn test_type_annotations() {
+    let source = "fun f(con: Http.Connection, cb: (Int) -> Unit): Result<Int, Str> {
+        let xs: [Int] = [];
+        var names: [Str: Str?] = [:];
+    }";
+    let module = parse_module(source);
+
+    let GroupMemberStatement::Fun(decl) = &module.decls[0] else {
+        panic!("Expected function declaration");
+    };
+    assert_eq!(decl.args[0].type_annot.to_string(), "Http.Connection");
+    assert_eq!(decl.args[1].type_annot.to_string(), "(Int) -> Unit");
+    assert_eq!(decl.ret_type.as_ref().unwrap().to_string(), "Result<Int, Str>");
+}
+
+#[test]
+fn test_invalid_types() {
+    let err = Parser::new("(Int, Str)").parse_type_expr().unwrap_err();
+    assert!(matches!(err.kind, ParseErrorKind::ExpectedToken));
+
+    let err = Parser::new("[Int").parse_type_expr().unwrap_err();
+    assert!(matches!(
+        err.kind,
+        ParseErrorKind::MissingTerminatingToken {
+            expected: Token::RightBracket,
+            ..
+        }
+    ));
+}
@@ -0,0 +1,58 @@
+use crate::parser::{BinOp, Expr, ExprKind, LiteralExpr, Parser, UnaryOp};
+use super::{extract_binary, parse_module};
+
+fn extract_unary(expr: Expr) -> (UnaryOp, Expr) {
+    match expr.kind {
+        ExprKind::Unary { op, val } => (op, *val),
+        _ => panic!("Expected unary expression"),
+    }
+}
+
+#[test]
+fn test_unary_ops() {
+    let (op, val) = extract_unary(Parser::new("-x").parse_expr().unwrap());
+    assert_eq!(op, UnaryOp::Negative);
+    assert!(matches!(val.kind, ExprKind::Read(ref name) if name == "x"));
+
+    // A negative number is a single literal
+    let expr = Parser::new("-1").parse_expr().unwrap();
+    assert!(matches!(expr.kind, ExprKind::Literal(LiteralExpr::Int(-1, None))));
+
+    let (op, _) = extract_unary(Parser::new("+x").parse_expr().unwrap());
+    assert_eq!(op, UnaryOp::Positive);
+
+    let (op, val) = extract_unary(Parser::new("!!quit").parse_expr().unwrap());
+    assert_eq!(op, UnaryOp::Not);
+    assert_eq!(extract_unary(val).0, UnaryOp::Not);
+}
+
+#[test]
+fn test_unary_binds_tighter_than_binary_looser_than_postfix() {
+    let (op, left, right) = extract_binary(Parser::new("-a.len() * -b").parse_expr().unwrap());
+    assert_eq!(op, BinOp::Mul);
+    let (_, val) = extract_unary(left);
+    assert!(matches!(val.kind, ExprKind::Call { .. }));
+    assert_eq!(extract_unary(right).0, UnaryOp::Negative);
+}
+
+#[test]
+fn test_logical_precedence() {
+    // a || (b && (c == d))
+    let (op, left, right) = extract_binary(Parser::new("a || b && c == d").parse_expr().unwrap());
+    assert_eq!(op, BinOp::Or);
+    assert!(matches!(left.kind, ExprKind::Read(_)));
+    let (op, _, right) = extract_binary(right);
+    assert_eq!(op, BinOp::And);
+    assert_eq!(extract_binary(right).0, BinOp::Equals);
+
+    // (a < b) && !c
+    let (op, left, right) = extract_binary(Parser::new("a < b && !c").parse_expr().unwrap());
+    assert_eq!(op, BinOp::And);
+    assert_eq!(extract_binary(left).0, BinOp::Lower);
+    assert_eq!(extract_unary(right).0, UnaryOp::Not);
+}
+
+#[test]
+fn test_while_not() {
+    parse_module("fun f() { while !quit { quit = done || -1 > x; } }");
+}